@@ -0,0 +1,309 @@
+use rawaccel_convert::types::{AccelArgs, Point, PointScaling};
+
+use crate::gui::try_get_point;
+
+//input speeds in counts/ms
+#[derive(Clone, Copy, PartialEq)]
+pub struct SpeedRange {
+    pub start: f64,
+    pub end: f64,
+}
+
+//the curve at one input speed, velocity is in counts/ms
+pub struct Readout {
+    pub sens: f64,
+    pub velocity: f64,
+    pub gain: f64,
+}
+
+//`None` where the curve can't be evaluated
+pub fn readout(args: &AccelArgs, speed: f64) -> Option<Readout> {
+    let mut sens_args = args.clone();
+    sens_args.point_scaling = PointScaling::Sens;
+    let velocity_at = |speed: f64| -> Option<f64> {
+        return try_get_point(speed, &sens_args)
+            .ok()
+            .map(|sens| speed * sens);
+    };
+    let sens = try_get_point(speed, &sens_args).ok()?;
+    //gain is the slope of the velocity curve
+    let step = (speed * 1e-3).max(1e-4);
+    let low = (speed - step).max(0.0);
+    let high = speed + step;
+    let gain = (velocity_at(high)? - velocity_at(low)?) / (high - low);
+    return Some(Readout {
+        sens,
+        velocity: speed * sens,
+        gain,
+    });
+}
+
+//ranges where output velocity drops as input speed rises, meaning gain is negative there
+pub fn decreasing_velocity_ranges(
+    points: &[Point],
+    point_scaling: &PointScaling,
+) -> Vec<SpeedRange> {
+    let velocities = points
+        .iter()
+        .map(|point| match point_scaling {
+            PointScaling::Sens | PointScaling::LookupSens => (point.x, point.x * point.y),
+            _ => (point.x, point.y),
+        })
+        .collect::<Vec<(f64, f64)>>();
+
+    let mut ranges: Vec<SpeedRange> = vec![];
+    for pair in velocities.windows(2) {
+        let (start, start_velocity) = pair[0];
+        let (end, end_velocity) = pair[1];
+        //ignore float noise on flat sections
+        let tolerance = start_velocity.abs().max(1.0) * 1e-9;
+        if end_velocity >= start_velocity - tolerance {
+            continue;
+        }
+        match ranges.last_mut() {
+            Some(last) if last.end == start => last.end = end,
+            _ => ranges.push(SpeedRange { start, end }),
+        }
+    }
+    return ranges;
+}
+
+//speeds `sens_samples` evaluates, fine enough to catch dips that coarse optimized curve points step over
+const DENSE_SAMPLES: usize = 2048;
+
+//the sens curve of `args` at evenly spaced speeds from 0 to `max_speed`, skipping speeds where it can't be evaluated
+pub fn sens_samples(args: &AccelArgs, max_speed: f64) -> Vec<Point> {
+    let mut sens_args = args.clone();
+    sens_args.point_scaling = PointScaling::Sens;
+    return (1..=DENSE_SAMPLES)
+        .filter_map(|i| {
+            let x = max_speed * i as f64 / DENSE_SAMPLES as f64;
+            try_get_point(x, &sens_args).ok().map(|y| Point { x, y })
+        })
+        .collect();
+}
+
+//evenly spaced starting samples for `adaptive_samples`
+const INITIAL_SAMPLES: usize = 64;
+//each starting interval is split at most this many times
+const MAX_SUBDIVISIONS: u32 = 6;
+//how far off the straight line between two samples the middle may be, as a fraction of the plotted extent
+const BEND_TOLERANCE: f64 = 0.002;
+
+//samples `point_at` for `t` from 0 to 1 and keeps splitting intervals where the plotted curve bends
+//`point_at` returns the point in plot coordinates, `None` where the curve can't be evaluated
+pub fn adaptive_samples(point_at: impl Fn(f64) -> Option<[f64; 2]>) -> Vec<[f64; 2]> {
+    let initial = (0..=INITIAL_SAMPLES)
+        .map(|i| {
+            let t = i as f64 / INITIAL_SAMPLES as f64;
+            (
+                t,
+                point_at(t).filter(|[x, y]| x.is_finite() && y.is_finite()),
+            )
+        })
+        .collect::<Vec<(f64, Option<[f64; 2]>)>>();
+
+    let mut min = [f64::MAX; 2];
+    let mut max = [f64::MIN; 2];
+    for [x, y] in initial.iter().filter_map(|(_, point)| *point) {
+        min = [min[0].min(x), min[1].min(y)];
+        max = [max[0].max(x), max[1].max(y)];
+    }
+    let extent = [(max[0] - min[0]).max(1e-9), (max[1] - min[1]).max(1e-9)];
+
+    let mut samples = vec![];
+    for pair in initial.windows(2) {
+        let (start_t, start) = pair[0];
+        let (end_t, end) = pair[1];
+        if let Some(start) = start {
+            samples.push(start);
+            if let Some(end) = end {
+                subdivide(
+                    &point_at,
+                    extent,
+                    (start_t, start),
+                    (end_t, end),
+                    0,
+                    &mut samples,
+                );
+            }
+        }
+    }
+    if let Some((_, Some(last))) = initial.last() {
+        samples.push(*last);
+    }
+    return samples;
+}
+
+//pushes the samples strictly between `start` and `end`
+fn subdivide(
+    point_at: &impl Fn(f64) -> Option<[f64; 2]>,
+    extent: [f64; 2],
+    start: (f64, [f64; 2]),
+    end: (f64, [f64; 2]),
+    depth: u32,
+    samples: &mut Vec<[f64; 2]>,
+) {
+    if depth >= MAX_SUBDIVISIONS {
+        return;
+    }
+    let middle_t = (start.0 + end.0) / 2.0;
+    let middle = match point_at(middle_t).filter(|[x, y]| x.is_finite() && y.is_finite()) {
+        Some(some) => some,
+        None => return,
+    };
+    let bend = (0..2)
+        .map(|axis| ((start.1[axis] + end.1[axis]) / 2.0 - middle[axis]).abs() / extent[axis])
+        .fold(0.0, f64::max);
+    if bend <= BEND_TOLERANCE {
+        return;
+    }
+    subdivide(
+        point_at,
+        extent,
+        start,
+        (middle_t, middle),
+        depth + 1,
+        samples,
+    );
+    samples.push(middle);
+    subdivide(
+        point_at,
+        extent,
+        (middle_t, middle),
+        end,
+        depth + 1,
+        samples,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rawaccel_convert::types::AccelMode;
+
+    fn points(coordinates: &[(f64, f64)]) -> Vec<Point> {
+        return coordinates.iter().map(|&(x, y)| Point { x, y }).collect();
+    }
+
+    fn bounds(ranges: &[SpeedRange]) -> Vec<(f64, f64)> {
+        return ranges
+            .iter()
+            .map(|range| (range.start, range.end))
+            .collect();
+    }
+
+    #[test]
+    fn adjacent_decreasing_ranges_merge() {
+        let velocity = points(&[
+            (1.0, 1.0),
+            (2.0, 3.0),
+            (3.0, 2.0),
+            (4.0, 1.5),
+            (5.0, 4.0),
+            (6.0, 3.0),
+        ]);
+        let ranges = decreasing_velocity_ranges(&velocity, &PointScaling::Velocity);
+        assert_eq!(bounds(&ranges), [(2.0, 4.0), (5.0, 6.0)]);
+    }
+
+    #[test]
+    fn flat_sections_are_not_decreasing() {
+        let velocity = points(&[
+            (1.0, 5.0),
+            (2.0, 5.0 - 1e-12),
+            (3.0, 5.0),
+            (4.0, 5.0 - 1e-3),
+        ]);
+        let ranges = decreasing_velocity_ranges(&velocity, &PointScaling::Velocity);
+        assert_eq!(bounds(&ranges), [(3.0, 4.0)]);
+    }
+
+    #[test]
+    fn sens_points_are_scaled_to_velocity() {
+        //sens halving while speed doubles keeps velocity flat, dropping faster makes it fall
+        let sens = points(&[(1.0, 2.0), (2.0, 1.0), (4.0, 0.25)]);
+        assert_eq!(
+            bounds(&decreasing_velocity_ranges(&sens, &PointScaling::Sens)),
+            [(2.0, 4.0)]
+        );
+        assert_eq!(
+            bounds(&decreasing_velocity_ranges(
+                &sens,
+                &PointScaling::LookupSens
+            )),
+            [(2.0, 4.0)]
+        );
+        //the same numbers read as velocity fall from the start
+        assert_eq!(
+            bounds(&decreasing_velocity_ranges(&sens, &PointScaling::Velocity)),
+            [(1.0, 4.0)]
+        );
+    }
+
+    #[test]
+    fn dense_samples_catch_a_narrow_drop() {
+        //sens steps down to 0.25 at 20 counts/ms, between coarse points this would be missed
+        let args = AccelArgs {
+            mode: AccelMode::Jump,
+            cap: Point { x: 20.0, y: 0.25 },
+            smooth: 0.0,
+            ..AccelArgs::default()
+        };
+        let samples = sens_samples(&args, 60.0);
+        assert_eq!(samples.len(), DENSE_SAMPLES);
+        assert_eq!(samples.last().map(|point| point.x), Some(60.0));
+        let ranges = decreasing_velocity_ranges(&samples, &PointScaling::Sens);
+        assert_eq!(ranges.len(), 1);
+        assert!(ranges[0].start < 20.0 && ranges[0].end >= 20.0);
+        assert!(ranges[0].end - ranges[0].start < 0.1);
+    }
+
+    #[test]
+    fn straight_lines_keep_the_starting_samples() {
+        let samples = adaptive_samples(|t| Some([t * 10.0, 2.0 * t + 1.0]));
+        assert_eq!(samples.len(), INITIAL_SAMPLES + 1);
+        assert_eq!(samples.first(), Some(&[0.0, 1.0]));
+        assert_eq!(samples.last(), Some(&[10.0, 3.0]));
+    }
+
+    #[test]
+    fn kinks_are_refined() {
+        //a kink between two starting samples
+        let kink = 0.3 + 0.5 / INITIAL_SAMPLES as f64;
+        let samples = adaptive_samples(|t| Some([t, (t - kink).abs()]));
+        assert!(samples.len() > INITIAL_SAMPLES + 1);
+        //extra samples only go around the kink
+        let interval = 1.0 / INITIAL_SAMPLES as f64;
+        let extra = samples
+            .iter()
+            .filter(|[x, _]| (x * INITIAL_SAMPLES as f64).fract() != 0.0)
+            .collect::<Vec<&[f64; 2]>>();
+        assert!(!extra.is_empty());
+        assert!(extra.iter().all(|[x, _]| (x - kink).abs() < interval));
+        //splitting stops once the bend is within tolerance, well before reaching the kink itself
+        let closest = samples
+            .iter()
+            .map(|[x, _]| (x - kink).abs())
+            .fold(f64::MAX, f64::min);
+        assert!(closest < interval / 4.0);
+        assert!(samples.windows(2).all(|pair| pair[0][0] < pair[1][0]));
+    }
+
+    #[test]
+    fn gaps_split_the_samples() {
+        let samples = adaptive_samples(|t| (!(0.4..0.6).contains(&t)).then_some([t, t * t]));
+        assert!(samples.iter().all(|[x, _]| !(0.4..0.6).contains(x)));
+        //nothing is interpolated across the gap
+        let largest_step = samples
+            .windows(2)
+            .map(|pair| pair[1][0] - pair[0][0])
+            .fold(0.0, f64::max);
+        assert!(largest_step > 0.2);
+        assert!(samples.windows(2).all(|pair| pair[0][0] < pair[1][0]));
+        let non_finite = adaptive_samples(|t| Some([t, 1.0 / (t - 0.5)]));
+        assert!(non_finite
+            .iter()
+            .all(|[x, y]| x.is_finite() && y.is_finite()));
+    }
+}
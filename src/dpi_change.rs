@@ -0,0 +1,187 @@
+use rawaccel_convert::types::{AccelArgs, AccelMode, Point};
+
+use crate::expression;
+use crate::fit;
+use crate::gui::RawaccelConvertSettings;
+use crate::parameters::{self, DpiScaling};
+
+fn scale_factor(dpi_scaling: DpiScaling, old_dpi: u32, new_dpi: u32) -> f64 {
+    let ratio = new_dpi as f64 / old_dpi as f64;
+    match dpi_scaling {
+        DpiScaling::Unchanged => 1.0,
+        DpiScaling::Speed => ratio,
+        DpiScaling::InverseSpeed => 1.0 / ratio,
+    }
+}
+
+//lut x values are input speeds, y values are too when the table is applied as velocity
+fn rescale_lookup_table(points: &[Point], velocity: bool, ratio: f64) -> Vec<Point> {
+    return points
+        .iter()
+        .map(|point| Point {
+            x: point.x * ratio,
+            y: match velocity {
+                true => point.y * ratio,
+                false => point.y,
+            },
+        })
+        .collect();
+}
+
+//rewrites every curve parameter of every mode so the curve over physical hand speed is unchanged
+//`lookup_velocity` is the lut's apply as setting, which only exists while look up table is the mode
+//without it the lut points are left as they are
+pub fn rescale_settings(
+    settings: &RawaccelConvertSettings,
+    lookup_velocity: Option<bool>,
+    old_dpi: u32,
+    new_dpi: u32,
+) -> RawaccelConvertSettings {
+    let mut new_settings = settings.clone();
+    new_settings.dpi_string = new_dpi.to_string();
+
+    for parameter in parameters::all_parameters() {
+        let factor = scale_factor(parameter.dpi_scaling, old_dpi, new_dpi);
+        let value_string = (parameter.settings_string)(&mut new_settings);
+        if let Ok(value) = expression::evaluate(value_string) {
+            if factor != 1.0 {
+                *value_string = fit::format_parameter(value * factor);
+            }
+        }
+    }
+
+    if let (Some(lookup_velocity), Some(points)) = (
+        lookup_velocity,
+        rawaccel_convert::args_parser::parse_lookup_table(&settings.lookup_table_string),
+    ) {
+        let ratio = new_dpi as f64 / old_dpi as f64;
+        let mut lookup_table_string = String::default();
+        for point in rescale_lookup_table(&points, lookup_velocity, ratio) {
+            lookup_table_string += &format!(
+                "{},{};\n",
+                fit::format_parameter(point.x),
+                fit::format_parameter(point.y)
+            );
+        }
+        new_settings.lookup_table_string = lookup_table_string;
+    }
+
+    return new_settings;
+}
+
+//the same change as `rescale_settings` applied to the parameters of the active mode
+pub fn rescale_args(args: &AccelArgs, old_dpi: u32, new_dpi: u32) -> AccelArgs {
+    let mut new_args = args.clone();
+    new_args.dpi = new_dpi;
+
+    let mut parameters = parameters::mode_parameters(args);
+    parameters.push(&parameters::SENS_MULTIPLIER);
+    for parameter in parameters {
+        let factor = scale_factor(parameter.dpi_scaling, old_dpi, new_dpi);
+        (parameter.set)(&mut new_args, (parameter.get)(args) * factor);
+    }
+
+    //gain is the lut's apply as setting only in look up table mode
+    if args.mode == AccelMode::Lookup {
+        new_args.lookup_data = rescale_lookup_table(
+            &args.lookup_data,
+            args.gain,
+            new_dpi as f64 / old_dpi as f64,
+        );
+    }
+
+    return new_args;
+}
+
+#[cfg(test)]
+mod tests {
+    use rawaccel_convert::types::CapMode;
+
+    use super::*;
+
+    #[test]
+    fn scale_factors() {
+        assert_eq!(scale_factor(DpiScaling::Unchanged, 800, 1600), 1.0);
+        assert_eq!(scale_factor(DpiScaling::Speed, 800, 1600), 2.0);
+        assert_eq!(scale_factor(DpiScaling::InverseSpeed, 800, 1600), 0.5);
+        assert_eq!(scale_factor(DpiScaling::Speed, 1600, 400), 0.25);
+    }
+
+    #[test]
+    fn rescale_args_scales_speeds_and_rates() {
+        let args = AccelArgs {
+            mode: AccelMode::Classic,
+            cap_mode: CapMode::Input,
+            dpi: 800,
+            acceleration: 0.01,
+            sens_multiplier: 1.2,
+            cap: Point { x: 20.0, y: 1.5 },
+            ..AccelArgs::default()
+        };
+        let new_args = rescale_args(&args, 800, 1600);
+        assert_eq!(new_args.dpi, 1600);
+        assert_eq!(new_args.acceleration, 0.005);
+        assert_eq!(new_args.sens_multiplier, 0.6);
+        assert_eq!(new_args.cap.x, 40.0);
+        assert_eq!(new_args.cap.y, 1.5);
+    }
+
+    #[test]
+    fn rescale_args_follows_apply_as_for_lookup() {
+        let mut args = AccelArgs {
+            mode: AccelMode::Lookup,
+            gain: true,
+            lookup_data: vec![Point { x: 10.0, y: 2.0 }],
+            ..AccelArgs::default()
+        };
+        let velocity = rescale_args(&args, 800, 1600);
+        assert_eq!(
+            (velocity.lookup_data[0].x, velocity.lookup_data[0].y),
+            (20.0, 4.0)
+        );
+
+        args.gain = false;
+        let sens = rescale_args(&args, 800, 1600);
+        assert_eq!((sens.lookup_data[0].x, sens.lookup_data[0].y), (20.0, 2.0));
+    }
+
+    #[test]
+    fn rescale_args_leaves_lookup_data_outside_lookup() {
+        let args = AccelArgs {
+            mode: AccelMode::Natural,
+            gain: true,
+            lookup_data: vec![Point { x: 10.0, y: 2.0 }],
+            ..AccelArgs::default()
+        };
+        let new_args = rescale_args(&args, 800, 1600);
+        assert_eq!(
+            (new_args.lookup_data[0].x, new_args.lookup_data[0].y),
+            (10.0, 2.0)
+        );
+    }
+
+    #[test]
+    fn rescale_settings_rewrites_parameter_strings() {
+        let settings = RawaccelConvertSettings {
+            acceleration_string: "0.01".to_string(),
+            cap_input_string: "10".to_string(),
+            cap_output_string: "1.5".to_string(),
+            ..RawaccelConvertSettings::default()
+        };
+        let new_settings = rescale_settings(&settings, None, 800, 1600);
+        assert_eq!(new_settings.dpi_string, "1600");
+        assert_eq!(new_settings.acceleration_string, "0.005");
+        assert_eq!(new_settings.cap_input_string, "20");
+        assert_eq!(new_settings.cap_output_string, "1.5");
+    }
+
+    #[test]
+    fn rescale_settings_keeps_lut_without_apply_as() {
+        let settings = RawaccelConvertSettings {
+            lookup_table_string: "10,2;".to_string(),
+            ..RawaccelConvertSettings::default()
+        };
+        let new_settings = rescale_settings(&settings, None, 800, 1600);
+        assert_eq!(new_settings.lookup_table_string, "10,2;");
+    }
+}
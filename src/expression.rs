@@ -0,0 +1,262 @@
+//arithmetic for numeric settings fields so values like `1600/800*1.5` can be typed directly
+//supports + - * / ^, parentheses and the constants pi, tau and e
+//a decimal comma is read like a decimal point since commas have no other meaning here
+
+const CONSTANTS: [(&str, f64); 3] = [
+    ("pi", std::f64::consts::PI),
+    ("tau", std::f64::consts::TAU),
+    ("e", std::f64::consts::E),
+];
+
+pub fn evaluate(text: &str) -> Result<f64, String> {
+    let mut parser = Parser {
+        chars: text.chars().filter(|char| !char.is_whitespace()).collect(),
+        position: 0,
+    };
+    if parser.chars.is_empty() {
+        return Err("Empty expression".to_string());
+    }
+    let value = parser.expression()?;
+    if let Some(char) = parser.peek() {
+        return Err(format!("Unexpected '{}'", char));
+    }
+    if !value.is_finite() {
+        return Err("Result is not a finite number".to_string());
+    }
+    return Ok(value);
+}
+
+//for dpi and point counts, the result has to come out as a whole number
+pub fn evaluate_whole(text: &str) -> Result<u32, String> {
+    let value = evaluate(text)?;
+    let rounded = value.round();
+    if (value - rounded).abs() > 1e-9 || rounded < 0.0 || rounded > u32::MAX as f64 {
+        return Err(format!("{} is not a whole number", value));
+    }
+    return Ok(rounded as u32);
+}
+
+//plain numbers don't need their value shown next to the field
+pub fn is_expression(text: &str) -> bool {
+    return text.trim().replace(',', ".").parse::<f64>().is_err();
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        return self.chars.get(self.position).copied();
+    }
+
+    fn eat(&mut self, char: char) -> bool {
+        if self.peek() == Some(char) {
+            self.position += 1;
+            return true;
+        }
+        return false;
+    }
+
+    fn expression(&mut self) -> Result<f64, String> {
+        let mut value = self.term()?;
+        loop {
+            if self.eat('+') {
+                value += self.term()?;
+            } else if self.eat('-') {
+                value -= self.term()?;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn term(&mut self) -> Result<f64, String> {
+        let mut value = self.unary()?;
+        loop {
+            if self.eat('*') {
+                value *= self.unary()?;
+            } else if self.eat('/') {
+                value /= self.unary()?;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    //`-2^2` is -4 like in most calculators
+    fn unary(&mut self) -> Result<f64, String> {
+        if self.eat('-') {
+            return Ok(-self.unary()?);
+        }
+        if self.eat('+') {
+            return self.unary();
+        }
+        return self.power();
+    }
+
+    //right associative, `2^3^2` is 2^9
+    fn power(&mut self) -> Result<f64, String> {
+        let base = self.primary()?;
+        if self.eat('^') {
+            return Ok(base.powf(self.unary()?));
+        }
+        return Ok(base);
+    }
+
+    fn primary(&mut self) -> Result<f64, String> {
+        match self.peek() {
+            Some('(') => {
+                self.position += 1;
+                let value = self.expression()?;
+                if !self.eat(')') {
+                    return Err("Missing ')'".to_string());
+                }
+                return Ok(value);
+            }
+            Some(char) if char.is_ascii_digit() || char == '.' || char == ',' => {
+                return self.number()
+            }
+            Some(char) if char.is_alphabetic() => return self.constant(),
+            Some(char) => return Err(format!("Unexpected '{}'", char)),
+            None => return Err("Expression ends early".to_string()),
+        }
+    }
+
+    fn number(&mut self) -> Result<f64, String> {
+        let start = self.position;
+        while self
+            .peek()
+            .is_some_and(|char| char.is_ascii_digit() || char == '.' || char == ',')
+        {
+            self.position += 1;
+        }
+        //exponent, only when digits follow so a trailing e is left for `constant` to reject
+        if matches!(self.peek(), Some('e' | 'E')) {
+            let digits_start = match self.chars.get(self.position + 1) {
+                Some('+' | '-') => self.position + 2,
+                _ => self.position + 1,
+            };
+            if self
+                .chars
+                .get(digits_start)
+                .is_some_and(|char| char.is_ascii_digit())
+            {
+                self.position = digits_start;
+                while self.peek().is_some_and(|char| char.is_ascii_digit()) {
+                    self.position += 1;
+                }
+            }
+        }
+        let text = self.chars[start..self.position]
+            .iter()
+            .map(|char| match char {
+                ',' => '.',
+                _ => *char,
+            })
+            .collect::<String>();
+        return text
+            .parse::<f64>()
+            .map_err(|_| format!("Invalid number '{}'", text));
+    }
+
+    fn constant(&mut self) -> Result<f64, String> {
+        let start = self.position;
+        while self.peek().is_some_and(|char| char.is_alphabetic()) {
+            self.position += 1;
+        }
+        let name = self.chars[start..self.position]
+            .iter()
+            .collect::<String>()
+            .to_lowercase();
+        return CONSTANTS
+            .iter()
+            .find(|(constant, _)| *constant == name)
+            .map(|(_, value)| *value)
+            .ok_or(format!("Unknown constant '{}'", name));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_evaluates(text: &str, expected: f64) {
+        let value = evaluate(text).unwrap();
+        assert!(
+            (value - expected).abs() < 1e-12,
+            "{} evaluated to {}, expected {}",
+            text,
+            value,
+            expected
+        );
+    }
+
+    #[test]
+    fn precedence() {
+        assert_evaluates("1600/800*1.5", 3.0);
+        assert_evaluates("1 + 2 * 3", 7.0);
+        assert_evaluates("(1 + 2) * 3", 9.0);
+        assert_evaluates("-2^2", -4.0);
+        assert_evaluates("2^-1", 0.5);
+        assert_evaluates("2^3^2", 512.0);
+        assert_evaluates("10 - 4 - 3", 3.0);
+    }
+
+    #[test]
+    fn decimal_comma() {
+        assert_evaluates("0,005", 0.005);
+        assert_evaluates("0,5 + 0.25", 0.75);
+        assert!(!is_expression("0,005"));
+        assert!(is_expression("1/2"));
+    }
+
+    #[test]
+    fn exponent_and_constant_e() {
+        assert_evaluates("1e3", 1000.0);
+        assert_evaluates("1E-3", 0.001);
+        assert_evaluates("2.5e+2", 250.0);
+        assert_evaluates("e", std::f64::consts::E);
+        assert_evaluates("2*e", 2.0 * std::f64::consts::E);
+        assert_evaluates("PI", std::f64::consts::PI);
+        assert_eq!(evaluate("2e"), Err("Unexpected 'e'".to_string()));
+        assert_eq!(evaluate("2e+"), Err("Unexpected 'e'".to_string()));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            evaluate("1/0"),
+            Err("Result is not a finite number".to_string())
+        );
+        assert_eq!(evaluate("(1"), Err("Missing ')'".to_string()));
+        assert_eq!(evaluate("1.2.3"), Err("Invalid number '1.2.3'".to_string()));
+        assert_eq!(evaluate(""), Err("Empty expression".to_string()));
+        assert_eq!(evaluate("  "), Err("Empty expression".to_string()));
+        assert_eq!(evaluate("1+"), Err("Expression ends early".to_string()));
+        assert_eq!(evaluate("1)"), Err("Unexpected ')'".to_string()));
+        assert_eq!(evaluate("foo"), Err("Unknown constant 'foo'".to_string()));
+    }
+
+    #[test]
+    fn whole_numbers() {
+        assert_eq!(evaluate_whole("1600"), Ok(1600));
+        assert_eq!(evaluate_whole("800*2"), Ok(1600));
+        assert_eq!(evaluate_whole("0"), Ok(0));
+        assert_eq!(evaluate_whole("4294967295"), Ok(u32::MAX));
+        assert_eq!(
+            evaluate_whole("1.5"),
+            Err("1.5 is not a whole number".to_string())
+        );
+        assert_eq!(
+            evaluate_whole("-1"),
+            Err("-1 is not a whole number".to_string())
+        );
+        assert_eq!(
+            evaluate_whole("4294967296"),
+            Err("4294967296 is not a whole number".to_string())
+        );
+        assert!(evaluate_whole("1/0").is_err());
+    }
+}
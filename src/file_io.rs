@@ -0,0 +1,63 @@
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_file(path: &str, contents: &[u8]) -> Result<(), String> {
+    return std::fs::write(path, contents).map_err(|err| format!("{}: {}", path, err));
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_file(path: &str) -> Result<Vec<u8>, String> {
+    return std::fs::read(path).map_err(|err| format!("{}: {}", path, err));
+}
+
+#[cfg(target_arch = "wasm32")]
+const DOWNLOAD_URL_LIFETIME_MS: i32 = 60_000;
+
+//the browser has no file system access so files are offered as a download instead
+#[cfg(target_arch = "wasm32")]
+pub fn save_file(path: &str, contents: &[u8]) -> Result<(), String> {
+    use eframe::wasm_bindgen::JsCast;
+
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(contents));
+    let blob =
+        web_sys::Blob::new_with_u8_array_sequence(&parts).map_err(|err| format!("{:?}", err))?;
+    let url =
+        web_sys::Url::create_object_url_with_blob(&blob).map_err(|err| format!("{:?}", err))?;
+    let anchor = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.create_element("a").ok())
+        .and_then(|element| element.dyn_into::<web_sys::HtmlAnchorElement>().ok())
+        .ok_or("Could not create download link".to_string())?;
+    anchor.set_href(&url);
+    anchor.set_download(path);
+    anchor.click();
+    //the download starts after this returns, revoking the url right away can cancel it
+    let revoke = eframe::wasm_bindgen::closure::Closure::once_into_js(move || {
+        let _ = web_sys::Url::revoke_object_url(&url);
+    });
+    return web_sys::window()
+        .ok_or("Could not schedule download cleanup".to_string())?
+        .set_timeout_with_callback_and_timeout_and_arguments_0(
+            revoke.unchecked_ref(),
+            DOWNLOAD_URL_LIFETIME_MS,
+        )
+        .map(|_| ())
+        .map_err(|err| format!("{:?}", err));
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn load_file(path: &str) -> Result<Vec<u8>, String> {
+    return Err(format!(
+        "{}: Files can't be opened by path in the browser, drop the file onto the window instead",
+        path
+    ));
+}
+
+//dropped files carry their bytes on the web and a path on native
+pub fn read_dropped_file(file: &egui::DroppedFile) -> Result<Vec<u8>, String> {
+    if let Some(bytes) = &file.bytes {
+        return Ok(bytes.to_vec());
+    }
+    match &file.path {
+        Some(path) => load_file(&path.to_string_lossy()),
+        None => Err(format!("{}: File has no contents", file.name)),
+    }
+}
@@ -0,0 +1,406 @@
+use rawaccel_convert::types::{AccelArgs, AccelMode, Point, PointScaling};
+
+use crate::gui::{get_point, RawaccelConvertSettings};
+use crate::parameters::{self, Parameter};
+
+const SOLVER_ITERATIONS: usize = 600;
+
+pub struct FitResult {
+    pub args: AccelArgs,
+    pub rms_error: f64,
+    pub max_error: f64,
+}
+
+pub struct ModeFit {
+    pub label: String,
+    pub parameters: Vec<&'static Parameter>,
+    pub result: FitResult,
+}
+
+//least squares fit of `parameters` so the sens curve of `args` passes through `targets`
+pub fn fit_parameters(args: &AccelArgs, parameters: &[&Parameter], targets: &[Point]) -> FitResult {
+    let mut base_args = args.clone();
+    base_args.point_scaling = PointScaling::Sens;
+
+    let to_search_space = |parameter: &Parameter, value: f64| {
+        let value = value.clamp(parameter.min, parameter.max);
+        match parameter.logarithmic {
+            true => value.max(f64::MIN_POSITIVE).ln(),
+            false => value,
+        }
+    };
+    let from_search_space = |parameter: &Parameter, value: f64| {
+        let value = match parameter.logarithmic {
+            true => value.exp(),
+            false => value,
+        };
+        value.clamp(parameter.min, parameter.max)
+    };
+    let apply = |search_values: &[f64]| {
+        let mut fitted_args = base_args.clone();
+        for (parameter, value) in parameters.iter().zip(search_values) {
+            (parameter.set)(&mut fitted_args, from_search_space(parameter, *value));
+        }
+        fitted_args
+    };
+    let objective = |search_values: &[f64]| {
+        let fitted_args = apply(search_values);
+        let mut error = 0.0;
+        for target in targets {
+            let y = get_point(target.x, &fitted_args);
+            if !y.is_finite() {
+                return f64::MAX;
+            }
+            error += (y - target.y).powi(2);
+        }
+        error
+    };
+
+    let start: Vec<f64> = parameters
+        .iter()
+        .map(|parameter| to_search_space(parameter, (parameter.get)(&base_args)))
+        .collect();
+    let steps: Vec<f64> = parameters
+        .iter()
+        .map(|parameter| match parameter.logarithmic {
+            true => 0.5,
+            false => (parameter.max - parameter.min) * 0.1,
+        })
+        .collect();
+
+    //restart from the best vertex a few times, nelder mead likes to stall on flat caps
+    let mut best = start;
+    let mut best_error = objective(&best);
+    if !parameters.is_empty() {
+        for _ in 0..4 {
+            let (candidate, candidate_error) =
+                nelder_mead(&objective, &best, &steps, SOLVER_ITERATIONS);
+            if candidate_error >= best_error {
+                break;
+            }
+            best = candidate;
+            best_error = candidate_error;
+        }
+    }
+
+    let mut fitted_args = apply(&best);
+    let mut max_error: f64 = 0.0;
+    for target in targets {
+        max_error = max_error.max((get_point(target.x, &fitted_args) - target.y).abs());
+    }
+    fitted_args.point_scaling = args.point_scaling.clone();
+    return FitResult {
+        args: fitted_args,
+        rms_error: (best_error / targets.len().max(1) as f64).sqrt(),
+        max_error,
+    };
+}
+
+pub fn parametric_modes() -> [AccelMode; 7] {
+    return [
+        AccelMode::Linear,
+        AccelMode::Classic,
+        AccelMode::Jump,
+        AccelMode::Natural,
+        AccelMode::Synchronous,
+        AccelMode::Motivity,
+        AccelMode::Power,
+    ];
+}
+
+//every parametric mode with and without gain, fit one per frame so the window stays responsive
+pub fn mode_fit_jobs() -> Vec<(AccelMode, bool)> {
+    let mut jobs = vec![];
+    for mode in parametric_modes() {
+        for gain in [false, true] {
+            jobs.push((mode.clone(), gain));
+        }
+    }
+    return jobs;
+}
+
+//fits the parameters that are free by default together with the sens multiplier
+pub fn fit_default_parameters(
+    args: &AccelArgs,
+    mode: &AccelMode,
+    gain: bool,
+    targets: &[Point],
+) -> ModeFit {
+    let mut parameters = parameters::mode_parameters(&mode_args(args, mode, gain));
+    parameters.retain(|parameter| parameter.free_by_default);
+    parameters.push(&parameters::SENS_MULTIPLIER);
+    return fit_mode(args, mode, gain, parameters, targets);
+}
+
+//best fit first
+pub fn rank_fits(fits: &mut [ModeFit]) {
+    fits.sort_by(|a, b| a.result.rms_error.total_cmp(&b.result.rms_error));
+}
+
+pub fn fit_mode(
+    args: &AccelArgs,
+    mode: &AccelMode,
+    gain: bool,
+    parameters: Vec<&'static Parameter>,
+    targets: &[Point],
+) -> ModeFit {
+    let result = fit_parameters(&mode_args(args, mode, gain), &parameters, targets);
+    return ModeFit {
+        label: mode_label(mode, gain),
+        parameters,
+        result,
+    };
+}
+
+fn mode_args(args: &AccelArgs, mode: &AccelMode, gain: bool) -> AccelArgs {
+    let mut mode_args = args.clone();
+    mode_args.mode = mode.clone();
+    mode_args.gain = gain;
+    if *mode == AccelMode::Linear {
+        mode_args.exponent_classic = 2.0;
+    }
+    return mode_args;
+}
+
+pub fn mode_label(mode: &AccelMode, gain: bool) -> String {
+    let name = match mode {
+        AccelMode::Noaccel => "Off".to_string(),
+        AccelMode::Lookup => "Look Up Table".to_string(),
+        _ => format!("{:?}", mode),
+    };
+    match gain {
+        true => format!("{} (Gain)", name),
+        false => name,
+    }
+}
+
+//writes the fitted values back into the settings strings shown in the sidebar
+pub fn apply_fit(
+    settings: &mut RawaccelConvertSettings,
+    parameters: &[&Parameter],
+    fitted_args: &AccelArgs,
+) {
+    for parameter in parameters {
+        *(parameter.settings_string)(settings) = format_parameter((parameter.get)(fitted_args));
+    }
+}
+
+//rounds to 5 significant figures
+pub fn format_parameter(value: f64) -> String {
+    match format!("{:.4e}", value).parse::<f64>() {
+        Ok(ok) => ok.to_string(),
+        Err(_) => value.to_string(),
+    }
+}
+
+fn nelder_mead(
+    objective: &impl Fn(&[f64]) -> f64,
+    start: &[f64],
+    steps: &[f64],
+    iterations: usize,
+) -> (Vec<f64>, f64) {
+    let dimensions = start.len();
+    let mut simplex: Vec<(Vec<f64>, f64)> = vec![(start.to_vec(), objective(start))];
+    for i in 0..dimensions {
+        let mut vertex = start.to_vec();
+        vertex[i] += steps[i];
+        let error = objective(&vertex);
+        simplex.push((vertex, error));
+    }
+
+    for _ in 0..iterations {
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+        if (simplex[dimensions].1 - simplex[0].1).abs() < 1e-14 {
+            break;
+        }
+
+        let mut centroid = vec![0.0; dimensions];
+        for (vertex, _) in &simplex[..dimensions] {
+            for i in 0..dimensions {
+                centroid[i] += vertex[i] / dimensions as f64;
+            }
+        }
+        let towards = |factor: f64| -> Vec<f64> {
+            (0..dimensions)
+                .map(|i| centroid[i] + factor * (simplex[dimensions].0[i] - centroid[i]))
+                .collect()
+        };
+
+        let reflected = towards(-1.0);
+        let reflected_error = objective(&reflected);
+        if reflected_error < simplex[0].1 {
+            let expanded = towards(-2.0);
+            let expanded_error = objective(&expanded);
+            simplex[dimensions] = match expanded_error < reflected_error {
+                true => (expanded, expanded_error),
+                false => (reflected, reflected_error),
+            };
+        } else if reflected_error < simplex[dimensions - 1].1 {
+            simplex[dimensions] = (reflected, reflected_error);
+        } else {
+            let contracted = towards(0.5);
+            let contracted_error = objective(&contracted);
+            if contracted_error < simplex[dimensions].1 {
+                simplex[dimensions] = (contracted, contracted_error);
+            } else {
+                let best = simplex[0].0.clone();
+                for (vertex, error) in simplex.iter_mut().skip(1) {
+                    for i in 0..dimensions {
+                        vertex[i] = best[i] + 0.5 * (vertex[i] - best[i]);
+                    }
+                    *error = objective(vertex);
+                }
+            }
+        }
+    }
+
+    simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let (vertex, error) = simplex.swap_remove(0);
+    return (vertex, error);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mode_parameter(args: &AccelArgs, id: &str) -> &'static Parameter {
+        return parameters::mode_parameters(args)
+            .into_iter()
+            .find(|parameter| parameter.id == id)
+            .unwrap();
+    }
+
+    fn samples(args: &AccelArgs) -> Vec<Point> {
+        return (1..=30)
+            .map(|i| {
+                let x = i as f64 * 2.0;
+                Point {
+                    x,
+                    y: get_point(x, args),
+                }
+            })
+            .collect();
+    }
+
+    #[test]
+    fn fitting_own_samples_recovers_parameters() {
+        let target_args = AccelArgs {
+            mode: AccelMode::Natural,
+            decay_rate: 0.05,
+            limit: 2.0,
+            ..AccelArgs::default()
+        };
+        let targets = samples(&target_args);
+        let start_args = AccelArgs {
+            decay_rate: 0.2,
+            limit: 1.0,
+            ..target_args.clone()
+        };
+        let fitted = fit_parameters(
+            &start_args,
+            &[
+                mode_parameter(&start_args, "decay"),
+                mode_parameter(&start_args, "limit"),
+            ],
+            &targets,
+        );
+        assert!((fitted.args.decay_rate - 0.05).abs() < 1e-4);
+        assert!((fitted.args.limit - 2.0).abs() < 1e-3);
+        assert!(fitted.rms_error < 1e-4);
+        assert!(fitted.max_error < 1e-3);
+    }
+
+    #[test]
+    fn default_parameters_include_sens_multiplier() {
+        let args = AccelArgs::default();
+        let natural_args = AccelArgs {
+            mode: AccelMode::Natural,
+            sens_multiplier: 1.5,
+            ..args.clone()
+        };
+        let mode_fit =
+            fit_default_parameters(&args, &AccelMode::Natural, false, &samples(&natural_args));
+        assert!(mode_fit
+            .parameters
+            .iter()
+            .any(|parameter| parameter.id == parameters::SENS_MULTIPLIER.id));
+        assert!(matches!(mode_fit.result.args.mode, AccelMode::Natural));
+        assert!(!mode_fit.result.args.gain);
+        assert!((mode_fit.result.args.sens_multiplier - 1.5).abs() < 1e-3);
+        assert!(mode_fit.result.rms_error < 1e-4);
+        assert_eq!(mode_fit.label, "Natural");
+    }
+
+    #[test]
+    fn fits_rank_by_rms_error() {
+        let mode_fit = |label: &str, rms_error: f64| ModeFit {
+            label: label.to_string(),
+            parameters: vec![],
+            result: FitResult {
+                args: AccelArgs::default(),
+                rms_error,
+                max_error: rms_error,
+            },
+        };
+        let mut fits = vec![
+            mode_fit("Classic", 0.3),
+            mode_fit("Natural", 0.01),
+            mode_fit("Power", 0.2),
+        ];
+        rank_fits(&mut fits);
+        let labels: Vec<&str> = fits.iter().map(|fit| fit.label.as_str()).collect();
+        assert_eq!(labels, ["Natural", "Power", "Classic"]);
+    }
+
+    #[test]
+    fn applied_fit_fills_settings_strings() {
+        let fitted_args = AccelArgs {
+            mode: AccelMode::Classic,
+            acceleration: 0.0123456789,
+            sens_multiplier: 1.5,
+            ..AccelArgs::default()
+        };
+        let mut settings = RawaccelConvertSettings::default();
+        apply_fit(
+            &mut settings,
+            &[
+                mode_parameter(&fitted_args, "acceleration"),
+                &parameters::SENS_MULTIPLIER,
+            ],
+            &fitted_args,
+        );
+        assert_eq!(settings.acceleration_string, "0.012346");
+        assert_eq!(settings.sens_multiplier_string, "1.5");
+    }
+
+    #[test]
+    fn converting_classic_to_linear_keeps_the_curve() {
+        let classic_args = AccelArgs {
+            mode: AccelMode::Classic,
+            acceleration: 0.02,
+            exponent_classic: 2.0,
+            ..AccelArgs::default()
+        };
+        let current_args = AccelArgs {
+            exponent_classic: 3.0,
+            ..classic_args.clone()
+        };
+        let mode_fit = fit_default_parameters(
+            &current_args,
+            &AccelMode::Linear,
+            false,
+            &samples(&classic_args),
+        );
+        assert_eq!(mode_fit.result.args.exponent_classic, 2.0);
+        assert!(mode_fit.result.max_error < 1e-3);
+    }
+
+    #[test]
+    fn parameters_keep_five_significant_figures() {
+        assert_eq!(format_parameter(1.234567), "1.2346");
+        assert_eq!(format_parameter(123456.7), "123460");
+        assert_eq!(format_parameter(0.000123456), "0.00012346");
+        assert_eq!(format_parameter(-2.5), "-2.5");
+        assert_eq!(format_parameter(2.0), "2");
+    }
+}
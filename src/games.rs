@@ -0,0 +1,67 @@
+pub struct GamePreset {
+    pub name: &'static str,
+    //degrees turned per count at an in-game sens of 1
+    pub yaw: f64,
+}
+
+pub const GAME_PRESETS: [GamePreset; 9] = [
+    GamePreset {
+        name: "Counter-Strike 2",
+        yaw: 0.022,
+    },
+    GamePreset {
+        name: "Source Engine",
+        yaw: 0.022,
+    },
+    GamePreset {
+        name: "Quake",
+        yaw: 0.022,
+    },
+    GamePreset {
+        name: "Apex Legends",
+        yaw: 0.022,
+    },
+    GamePreset {
+        name: "Team Fortress 2",
+        yaw: 0.022,
+    },
+    GamePreset {
+        name: "Overwatch 2",
+        yaw: 0.0066,
+    },
+    GamePreset {
+        name: "Call of Duty",
+        yaw: 0.0066,
+    },
+    GamePreset {
+        name: "Valorant",
+        yaw: 0.07,
+    },
+    GamePreset {
+        name: "Fortnite (Config Sens)",
+        yaw: 0.5555,
+    },
+];
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct CustomGame {
+    pub name: String,
+    pub yaw: f64,
+}
+
+//shown instead of a game name once the yaw is edited by hand
+pub const CUSTOM_YAW_NAME: &str = "Custom";
+
+//yaw of the preset or saved custom game called `name`
+pub fn game_yaw(name: &str, custom_games: &[CustomGame]) -> Option<f64> {
+    return GAME_PRESETS
+        .iter()
+        .find(|preset| preset.name == name)
+        .map(|preset| preset.yaw)
+        .or_else(|| {
+            custom_games
+                .iter()
+                .find(|game| game.name == name)
+                .map(|game| game.yaw)
+        });
+}
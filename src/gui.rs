@@ -1,7 +1,40 @@
 use rawaccel_convert::types::{AccelArgs, AccelMode, CapMode, CurvegenResult, PointScaling};
 
-#[derive(serde::Deserialize, serde::Serialize, Clone)]
+use crate::curve_analysis;
+use crate::dpi_change;
+use crate::expression;
+use crate::file_io;
+use crate::fit;
+use crate::games;
+use crate::history::History;
+use crate::libinput_log;
+use crate::metrics;
+use crate::motion::{self, MotionRecording};
+use crate::number_format::{DecimalSeparator, FieldSeparator, NumberFormat, Precision};
+use crate::parameters::{self, Parameter};
+use crate::plot_image;
+use crate::script_export::{self, ScriptFormat};
+use crate::sweep;
+use crate::units::{self, OutputUnit, PlotTransform, PlotUnits};
+use crate::validation;
+
+const UNDO_SHORTCUT: egui::KeyboardShortcut =
+    egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Z);
+const REDO_SHORTCUT: egui::KeyboardShortcut = egui::KeyboardShortcut::new(
+    egui::Modifiers::COMMAND.plus(egui::Modifiers::SHIFT),
+    egui::Key::Z,
+);
+
+//raised when saved settings need migrating, see `parameters::migrate_legacy`
+pub const SETTINGS_VERSION: u32 = 1;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+#[serde(default)]
 pub struct RawaccelConvertSettings {
+    //0 when loaded from a version that didn't save it
+    #[serde(default)]
+    pub settings_version: u32,
+
     pub dark_mode: bool,
 
     pub point_count_string: String,
@@ -27,54 +60,70 @@ pub struct RawaccelConvertSettings {
     pub motivity_string: String,
     pub syncspeed_string: String,
 
+    pub growth_rate_string: String,
+    pub midpoint_string: String,
+
     pub scale_string: String,
     pub exponent_power_string: String,
     pub output_offset_string: String,
 
     pub lookup_table_string: String,
+
+    pub game_name: String,
+    pub game_yaw_string: String,
+    pub game_sens_string: String,
+    pub custom_games: Vec<games::CustomGame>,
+
+    pub number_format: NumberFormat,
 }
 
 impl Default for RawaccelConvertSettings {
     fn default() -> Self {
-        Self {
+        let mut settings = Self {
+            settings_version: SETTINGS_VERSION,
+
             dark_mode: true,
 
             point_count_string: "64".to_string(),
 
             //global
             dpi_string: "1200".to_string(),
-            sens_multiplier_string: "1.0".to_string(),
             curve_type_string: "Off".to_string(),
 
-            //linear/classic
-            acceleration_string: "0.005".to_string(),
-            cap_output_string: "1.5".to_string(),
-            cap_input_string: "15".to_string(),
-            input_offset_string: "0".to_string(),
-            exponent_classic_string: "2".to_string(),
-
-            //jump
-            smooth_string: "0.5".to_string(),
-            input_string: "15".to_string(),
-            output_string: "1.5".to_string(),
-
-            //natural
-            decay_string: "0.1".to_string(),
-            limit_string: "1.5".to_string(),
-
-            //synchronous
-            gamma_string: "1".to_string(),
-            motivity_string: "1.5".to_string(),
-            syncspeed_string: "5".to_string(),
-
-            //power
-            scale_string: "1".to_string(),
-            exponent_power_string: "0.05".to_string(),
-            output_offset_string: "0".to_string(),
+            //curve parameters, filled in from the parameter registry below
+            sens_multiplier_string: String::default(),
+            acceleration_string: String::default(),
+            cap_output_string: String::default(),
+            cap_input_string: String::default(),
+            input_offset_string: String::default(),
+            exponent_classic_string: String::default(),
+            smooth_string: String::default(),
+            input_string: String::default(),
+            output_string: String::default(),
+            decay_string: String::default(),
+            limit_string: String::default(),
+            gamma_string: String::default(),
+            motivity_string: String::default(),
+            syncspeed_string: String::default(),
+            growth_rate_string: String::default(),
+            midpoint_string: String::default(),
+            scale_string: String::default(),
+            exponent_power_string: String::default(),
+            output_offset_string: String::default(),
 
             //lookup
             lookup_table_string: "".to_string(),
-        }
+
+            //game
+            game_name: "Counter-Strike 2".to_string(),
+            game_yaw_string: "0.022".to_string(),
+            game_sens_string: "1".to_string(),
+            custom_games: vec![],
+
+            number_format: NumberFormat::default(),
+        };
+        parameters::set_defaults(&mut settings);
+        return settings;
     }
 }
 
@@ -82,11 +131,21 @@ impl Default for RawaccelConvertSettings {
 #[serde(default)]
 pub struct RawaccelConvertGui {
     settings: RawaccelConvertSettings,
+    plot_units: PlotUnits,
+    parameter_sliders: bool,
+    hover_inspector: bool,
+    stacked_plots: StackedPlots,
 
     #[serde(skip)]
     accel_args: AccelArgs,
     #[serde(skip)]
     export_accel_args_cache: AccelArgs,
+    #[serde(skip)]
+    plot_samples_cache: Vec<CachedPlotSamples>,
+    #[serde(skip)]
+    game_yaw: f64,
+    #[serde(skip)]
+    game_sens: f64,
 
     #[serde(skip)]
     points: String,
@@ -98,15 +157,319 @@ pub struct RawaccelConvertGui {
     curvegen_export: CurvegenResult,
     #[serde(skip)]
     export_point_scaling: PointScaling,
+    #[serde(skip)]
+    history: History<EditSnapshot>,
+    #[serde(skip)]
+    solver: SolverState,
+    #[serde(skip)]
+    curve_fit: CurveFitState,
+    #[serde(skip)]
+    convert_mode: ConvertModeState,
+    #[serde(skip)]
+    dpi_wizard: DpiWizardState,
+    #[serde(skip)]
+    game_panel: GamePanelState,
+    #[serde(skip)]
+    motion_recording: MotionRecording,
+    #[serde(skip)]
+    recorder: RecorderState,
+    #[serde(skip)]
+    replay: ReplayState,
+    #[serde(skip)]
+    metrics: MetricsState,
+    #[serde(skip)]
+    sweep: SweepState,
+    #[serde(skip)]
+    dragged_handle: Option<&'static str>,
+    #[serde(skip)]
+    image_export: ImageExportState,
+    #[serde(skip)]
+    script_export: ScriptExportState,
+    #[serde(skip)]
+    problems: Vec<validation::Problem>,
+    #[serde(skip)]
+    decreasing_velocity_ranges: Vec<curve_analysis::SpeedRange>,
+    #[serde(skip)]
+    export_decreasing_velocity: bool,
+    #[serde(skip)]
+    curve_error: Option<String>,
+    #[serde(skip)]
+    export_error: Option<String>,
+}
+
+struct SolverState {
+    open: bool,
+    //speed and sens strings
+    targets: Vec<(String, String)>,
+    //ids of parameters whose free/fixed state differs from `Parameter::free_by_default`
+    toggled_parameters: Vec<&'static str>,
+    report: Option<SolverReport>,
+}
+
+struct SolverReport {
+    rms_error: f64,
+    max_error: f64,
+    achieved: Vec<rawaccel_convert::types::Point>,
+}
+
+#[derive(PartialEq)]
+enum FitSource {
+    LookupTable,
+    Pasted,
+}
+
+struct CurveFitState {
+    open: bool,
+    source: FitSource,
+    pasted_points: String,
+    points_are_velocity: bool,
+    targets: Vec<rawaccel_convert::types::Point>,
+    fits: Vec<fit::ModeFit>,
+    //parallel to `fits`
+    shown_fits: Vec<bool>,
+    error: Option<String>,
+    //modes still to fit and the args they are fit from, `fits` is filled once all are done
+    pending_fits: Vec<(AccelMode, bool)>,
+    running_fits: Vec<fit::ModeFit>,
+    fit_args: AccelArgs,
+}
+
+impl Default for CurveFitState {
+    fn default() -> Self {
+        Self {
+            open: false,
+            source: FitSource::LookupTable,
+            pasted_points: String::default(),
+            points_are_velocity: false,
+            targets: vec![],
+            fits: vec![],
+            shown_fits: vec![],
+            error: None,
+            pending_fits: vec![],
+            running_fits: vec![],
+            fit_args: AccelArgs::default(),
+        }
+    }
+}
+
+struct ConvertModeState {
+    open: bool,
+    mode: AccelMode,
+    gain: bool,
+    candidate: Option<fit::ModeFit>,
+}
+
+impl Default for ConvertModeState {
+    fn default() -> Self {
+        Self {
+            open: false,
+            mode: AccelMode::Natural,
+            gain: true,
+            candidate: None,
+        }
+    }
+}
+
+struct RecorderState {
+    open: bool,
+    recording: bool,
+    //set when raw mouse deltas weren't available and cursor movement was recorded instead
+    used_pointer_fallback: bool,
+    show_histogram: bool,
+    file_path: String,
+    status: Option<Result<String, String>>,
+}
+
+impl Default for RecorderState {
+    fn default() -> Self {
+        Self {
+            open: false,
+            recording: false,
+            used_pointer_fallback: false,
+            show_histogram: true,
+            file_path: "recording.csv".to_string(),
+            status: None,
+        }
+    }
+}
+
+struct ReplayState {
+    open: bool,
+    //curve pinned to compare the current one against
+    comparison: Option<AccelArgs>,
+    start_seconds: f64,
+    length_seconds: f64,
+}
+
+impl Default for ReplayState {
+    fn default() -> Self {
+        Self {
+            open: false,
+            comparison: None,
+            start_seconds: 0.0,
+            length_seconds: 1.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum MetricColumn {
+    MeanSens,
+    Cm360Spread,
+    Capped,
+    DecreasingVelocity,
+}
+
+struct MetricsState {
+    open: bool,
+    sort_by: MetricColumn,
+}
+
+impl Default for MetricsState {
+    fn default() -> Self {
+        Self {
+            open: false,
+            sort_by: MetricColumn::Cm360Spread,
+        }
+    }
+}
+
+//sens, velocity and gain on separate plots sharing the x axis, replaces the single plot when enabled
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+struct StackedPlots {
+    enabled: bool,
+    sens: bool,
+    velocity: bool,
+    gain: bool,
+}
+
+impl Default for StackedPlots {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            sens: true,
+            velocity: true,
+            gain: true,
+        }
+    }
+}
+
+struct ImageExportState {
+    open: bool,
+    //saved with .svg or .png appended
+    file_name: String,
+    //png size in pixels, svgs keep the same aspect ratio
+    width: u32,
+    height: u32,
+    status: Option<Result<String, String>>,
+}
+
+impl Default for ImageExportState {
+    fn default() -> Self {
+        Self {
+            open: false,
+            file_name: "curve".to_string(),
+            width: 1600,
+            height: 1000,
+            status: None,
+        }
+    }
+}
+
+struct ScriptExportState {
+    open: bool,
+    format: ScriptFormat,
+    //saved with the format's extension appended
+    file_name: String,
+    script: String,
+    closed_form: bool,
+    //what `script` was generated from
+    generated_for: Option<(AccelArgs, ScriptFormat)>,
+    status: Option<Result<String, String>>,
+}
+
+impl Default for ScriptExportState {
+    fn default() -> Self {
+        Self {
+            open: false,
+            format: ScriptFormat::Gnuplot,
+            file_name: "curve".to_string(),
+            script: String::default(),
+            closed_form: false,
+            generated_for: None,
+            status: None,
+        }
+    }
+}
+
+#[derive(Default)]
+struct SweepState {
+    open: bool,
+    parameter_id: &'static str,
+    from_string: String,
+    to_string: String,
+    count_string: String,
+    curves: Vec<sweep::SweepCurve>,
+    //what `curves` were generated from, the swept parameter is zeroed in the args
+    generated_for: Option<(AccelArgs, &'static str, Vec<f64>)>,
+    //how far the scrubber is between the first and last value
+    scrub_position: f64,
+    playing: bool,
+    playing_backwards: bool,
+}
+
+#[derive(Default)]
+struct GamePanelState {
+    open: bool,
+    degrees_per_cm: bool,
+    custom_game_name: String,
+}
+
+#[derive(Default)]
+struct DpiWizardState {
+    open: bool,
+    old_dpi_string: String,
+    new_dpi_string: String,
+}
+
+impl Default for SolverState {
+    fn default() -> Self {
+        Self {
+            open: false,
+            targets: vec![
+                ("5".to_string(), "1.0".to_string()),
+                ("40".to_string(), "2.0".to_string()),
+            ],
+            toggled_parameters: vec![],
+            report: None,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+struct EditSnapshot {
+    //in the order of `curve_settings_strings`
+    settings_strings: Vec<String>,
+    mode: AccelMode,
+    cap_mode: CapMode,
+    gain: bool,
 }
 
 impl Default for RawaccelConvertGui {
     fn default() -> Self {
         Self {
             settings: RawaccelConvertSettings::default(),
+            plot_units: PlotUnits::default(),
+            parameter_sliders: true,
+            hover_inspector: true,
+            stacked_plots: StackedPlots::default(),
 
             accel_args: AccelArgs::default(),
             export_accel_args_cache: AccelArgs::default(),
+            plot_samples_cache: vec![],
+            game_yaw: 0.022,
+            game_sens: 1.0,
 
             points: String::default(),
             libinput_steps: String::default(),
@@ -119,6 +482,25 @@ impl Default for RawaccelConvertGui {
                 step_size: 1.0,
             },
             export_point_scaling: PointScaling::Sens,
+            history: History::default(),
+            solver: SolverState::default(),
+            curve_fit: CurveFitState::default(),
+            convert_mode: ConvertModeState::default(),
+            dpi_wizard: DpiWizardState::default(),
+            game_panel: GamePanelState::default(),
+            motion_recording: MotionRecording::default(),
+            recorder: RecorderState::default(),
+            replay: ReplayState::default(),
+            metrics: MetricsState::default(),
+            sweep: SweepState::default(),
+            dragged_handle: None,
+            image_export: ImageExportState::default(),
+            script_export: ScriptExportState::default(),
+            problems: vec![],
+            decreasing_velocity_ranges: vec![],
+            export_decreasing_velocity: false,
+            curve_error: None,
+            export_error: None,
         }
     }
 }
@@ -127,7 +509,10 @@ impl RawaccelConvertGui {
     pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
         //restore state
         if let Some(storage) = cc.storage {
-            return eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default();
+            let mut rawaccel_convert_gui: RawaccelConvertGui =
+                eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default();
+            parameters::migrate_legacy(&mut rawaccel_convert_gui.settings);
+            return rawaccel_convert_gui;
         }
 
         return Default::default();
@@ -143,6 +528,13 @@ impl eframe::App for RawaccelConvertGui {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         set_theme(ctx, self.settings.dark_mode);
 
+        //consumed before any text field sees them so they don't trigger text edit undo
+        if ctx.input_mut(|i| i.consume_shortcut(&REDO_SHORTCUT)) {
+            redo(self);
+        } else if ctx.input_mut(|i| i.consume_shortcut(&UNDO_SHORTCUT)) {
+            undo(self);
+        }
+
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             egui::menu::bar(ui, |ui| {
                 ui.with_layout(egui::Layout::left_to_right(egui::Align::Center), |ui| {
@@ -152,8 +544,100 @@ impl eframe::App for RawaccelConvertGui {
                         }
                     });
 
+                    ui.menu_button("Edit", |ui| {
+                        if ui
+                            .add_enabled(
+                                self.history.can_undo(),
+                                egui::Button::new("Undo")
+                                    .shortcut_text(ctx.format_shortcut(&UNDO_SHORTCUT)),
+                            )
+                            .clicked()
+                        {
+                            undo(self);
+                            ui.close_menu();
+                        }
+
+                        if ui
+                            .add_enabled(
+                                self.history.can_redo(),
+                                egui::Button::new("Redo")
+                                    .shortcut_text(ctx.format_shortcut(&REDO_SHORTCUT)),
+                            )
+                            .clicked()
+                        {
+                            redo(self);
+                            ui.close_menu();
+                        }
+                    });
+
+                    ui.menu_button("Tools", |ui| {
+                        if ui.button("Parameter Solver").clicked() {
+                            self.solver.open = !self.solver.open;
+                            ui.close_menu();
+                        }
+
+                        if ui.button("Curve Fit").clicked() {
+                            self.curve_fit.open = !self.curve_fit.open;
+                            ui.close_menu();
+                        }
+
+                        if ui.button("Convert to Mode…").clicked() {
+                            self.convert_mode.open = !self.convert_mode.open;
+                            self.convert_mode.gain = self.accel_args.gain;
+                            self.convert_mode.candidate = None;
+                            ui.close_menu();
+                        }
+
+                        if ui.button("Game Sensitivity").clicked() {
+                            self.game_panel.open = !self.game_panel.open;
+                            ui.close_menu();
+                        }
+
+                        if ui.button("Motion Recorder").clicked() {
+                            self.recorder.open = !self.recorder.open;
+                            ui.close_menu();
+                        }
+
+                        if ui.button("Motion Replay").clicked() {
+                            self.replay.open = !self.replay.open;
+                            ui.close_menu();
+                        }
+
+                        if ui.button("Usage Metrics").clicked() {
+                            self.metrics.open = !self.metrics.open;
+                            ui.close_menu();
+                        }
+
+                        if ui.button("Parameter Sweep").clicked() {
+                            self.sweep.open = !self.sweep.open;
+                            ui.close_menu();
+                        }
+
+                        if ui.button("Export Image…").clicked() {
+                            self.image_export.open = !self.image_export.open;
+                            ui.close_menu();
+                        }
+
+                        if ui.button("Export Script…").clicked() {
+                            self.script_export.open = !self.script_export.open;
+                            ui.close_menu();
+                        }
+
+                        if ui.button("Change DPI…").clicked() {
+                            self.dpi_wizard.open = !self.dpi_wizard.open;
+                            self.dpi_wizard.old_dpi_string = self.settings.dpi_string.clone();
+                            self.dpi_wizard.new_dpi_string = self.settings.dpi_string.clone();
+                            ui.close_menu();
+                        }
+                    });
+
                     ui.menu_button("Settings", |ui| {
                         light_dark_buttons(self, ui);
+                        ui.checkbox(&mut self.parameter_sliders, "Parameter Sliders");
+                        ui.checkbox(&mut self.hover_inspector, "Hover Inspector");
+                        ui.menu_button("Number Format", |ui| {
+                            add_number_format(&mut self.settings.number_format, ui);
+                        });
 
                         if ui.button("Reset").clicked() {
                             self.settings = RawaccelConvertSettings::default();
@@ -174,275 +658,515 @@ impl eframe::App for RawaccelConvertGui {
             .max_width(250.0)
             .resizable(false)
             .show(ctx, |ui| {
-                egui::Grid::new("rawaccel_convert_gui_grid").show(ui, |ui| {
-                    add_dpi(self, ui);
-                    ui.end_row();
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::Grid::new("rawaccel_convert_gui_grid").show(ui, |ui| {
+                        add_dpi(self, ui);
+                        ui.end_row();
 
-                    add_sens_multiplier(self, ui);
-                    ui.end_row();
-
-                    add_curve_type(self, ui);
-                    ui.end_row();
+                        add_parameter(self, ui, &parameters::SENS_MULTIPLIER);
+                        ui.end_row();
 
-                    match self.accel_args.mode {
-                        AccelMode::Linear => {
-                            add_gain(self, ui);
-                            ui.end_row();
+                        add_curve_type(self, ui);
+                        ui.end_row();
 
-                            match self.accel_args.cap_mode {
-                                rawaccel_convert::types::CapMode::InputOutput => {
-                                    add_cap_type(self, ui);
-                                    ui.end_row();
+                        match self.accel_args.mode {
+                            AccelMode::Lookup => {
+                                add_lookup_table_box(self, ui);
+                                ui.end_row();
 
-                                    add_cap_input(self, ui);
-                                    ui.end_row();
+                                add_apply_as(self, ui);
+                                ui.end_row();
+                            }
+                            AccelMode::Noaccel => {}
+                            _ => {
+                                add_gain(self, ui);
+                                ui.end_row();
 
-                                    add_cap_output(self, ui);
+                                let mode_parameters = parameters::mode_parameters(&self.accel_args);
+                                let (before_cap_type, after_cap_type): (Vec<_>, Vec<_>) =
+                                    mode_parameters
+                                        .into_iter()
+                                        .partition(|parameter| parameter.before_cap_type);
+                                for parameter in before_cap_type {
+                                    add_parameter(self, ui, parameter);
                                     ui.end_row();
                                 }
-                                rawaccel_convert::types::CapMode::Input => {
-                                    add_acceleration(self, ui);
-                                    ui.end_row();
 
+                                if parameters::has_cap(&self.accel_args.mode) {
                                     add_cap_type(self, ui);
                                     ui.end_row();
-
-                                    add_cap_input(self, ui);
-                                    ui.end_row();
                                 }
-                                rawaccel_convert::types::CapMode::Output => {
-                                    add_acceleration(self, ui);
-                                    ui.end_row();
-
-                                    add_cap_type(self, ui);
-                                    ui.end_row();
 
-                                    add_cap_output(self, ui);
+                                for parameter in after_cap_type {
+                                    add_parameter(self, ui, parameter);
                                     ui.end_row();
                                 }
                             }
+                        }
 
-                            add_input_offset(self, ui);
-                            ui.end_row();
-
+                        if self.accel_args.mode == AccelMode::Linear {
                             self.accel_args.exponent_classic = 2.0;
                         }
-                        AccelMode::Classic => {
-                            add_gain(self, ui);
-                            ui.end_row();
-
-                            match self.accel_args.cap_mode {
-                                rawaccel_convert::types::CapMode::InputOutput => {
-                                    add_cap_type(self, ui);
-                                    ui.end_row();
+                    });
+                    self.problems = validation::validate(
+                        &self.settings,
+                        &self.accel_args,
+                        &self.export_point_scaling,
+                    );
+                    add_problems(self, ui);
+                    add_points_dump(self, ui);
+                });
+            });
 
-                                    add_cap_input(self, ui);
-                                    ui.end_row();
+        add_solver_window(self, ctx);
+        add_curve_fit_window(self, ctx);
+        add_convert_mode_window(self, ctx);
+        add_dpi_wizard_window(self, ctx);
+        add_game_window(self, ctx);
+        add_recorder_window(self, ctx);
+        add_replay_window(self, ctx);
+        add_metrics_window(self, ctx);
+        add_sweep_window(self, ctx);
+        add_image_export_window(self, ctx);
+        add_script_export_window(self, ctx);
 
-                                    add_cap_output(self, ui);
-                                    ui.end_row();
-                                }
-                                rawaccel_convert::types::CapMode::Input => {
-                                    add_acceleration(self, ui);
-                                    ui.end_row();
+        egui::CentralPanel::default().show(ctx, |ui| {
+            let plot_accel_args = self.accel_args.clone();
+            let plot_bounds = get_bounds(self, &plot_accel_args);
+            let curve_error = self
+                .curve_error
+                .clone()
+                .or_else(|| plot_bounds.as_ref().err().cloned());
+            let plot_bounds = plot_bounds.unwrap_or_else(|_| fallback_bounds(&plot_accel_args));
+            let transform = plot_transform(self);
+            if self.stacked_plots.enabled {
+                add_stacked_plots(self, ui, plot_bounds.1[0], &transform);
+                return;
+            }
+            let transformed_points = cached_plot_samples(
+                &mut self.plot_samples_cache,
+                &plot_accel_args,
+                plot_bounds.0[0],
+                plot_bounds.1[0],
+                &transform,
+            );
+            let transformed_bounds = transform_bounds(plot_bounds, &transformed_points, &transform);
+            let (plot_areas, plot_lines) = central_plot_items(
+                self,
+                &plot_accel_args,
+                plot_bounds,
+                curve_error.is_none().then_some(transformed_points),
+                transformed_bounds,
+                &transform,
+            );
+            let hover_curves = match self.hover_inspector {
+                true => plotted_curves(self),
+                false => vec![],
+            };
+            let mut plot = egui_plot::Plot::new("lines_demo")
+                .legend(egui_plot::Legend::default())
+                .show_axes(true)
+                .show_grid(true);
+            plot = plot
+                .coordinates_formatter(
+                    egui_plot::Corner::LeftBottom,
+                    egui_plot::CoordinatesFormatter::new(move |point, _bounds| {
+                        format_plot_coordinates(point.x, point.y, &transform)
+                    }),
+                )
+                .label_formatter({
+                    //the hover inspector already shows every curve under the pointer
+                    let hover_inspector = self.hover_inspector;
+                    move |_name, point| match hover_inspector {
+                        true => String::default(),
+                        false => format_plot_coordinates(point.x, point.y, &transform),
+                    }
+                })
+                .custom_x_axes(plot_x_axes(&transform))
+                .custom_y_axes(plot_y_axes(&transform))
+                .allow_zoom(false)
+                .allow_drag(false)
+                .allow_scroll(false);
+            if transform.units.log_x {
+                plot = plot.x_grid_spacer(log_grid_spacer);
+            }
+            if transform.units.log_y {
+                plot = plot.y_grid_spacer(log_grid_spacer);
+            }
+            let plot_response = plot.show(ui, |plot_ui| {
+                plot_ui.set_plot_bounds(egui_plot::PlotBounds::from_min_max(
+                    transformed_bounds.0,
+                    transformed_bounds.1,
+                ));
+                for area in plot_areas {
+                    add_plot_area(plot_ui, area);
+                }
+                for line in plot_lines {
+                    add_plot_line(plot_ui, line);
+                }
+                if let Some(curve_error) = &curve_error {
+                    plot_ui.text(egui_plot::Text::new(
+                        egui_plot::PlotPoint::new(
+                            (transformed_bounds.0[0] + transformed_bounds.1[0]) / 2.0,
+                            (transformed_bounds.0[1] + transformed_bounds.1[1]) / 2.0,
+                        ),
+                        egui::RichText::new(format!("Curve invalid\n{}", curve_error))
+                            .size(18.0)
+                            .color(egui::Color32::from_rgb(220, 60, 60)),
+                    ));
+                }
+                if curve_error.is_none() {
+                    add_parameter_handles(self, &plot_accel_args, &transform, plot_ui);
+                }
+                let pointer = plot_ui.pointer_coordinate();
+                if let (true, Some(pointer)) = (self.hover_inspector, pointer) {
+                    plot_ui.vline(
+                        egui_plot::VLine::new(pointer.x)
+                            .color(plot_ui.ctx().style().visuals.weak_text_color()),
+                    );
+                }
+                pointer
+            });
+            if let (true, Some(pointer)) = (self.hover_inspector, plot_response.inner) {
+                if self.dragged_handle.is_none() {
+                    plot_response.response.on_hover_ui_at_pointer(|ui| {
+                        add_hover_inspector(
+                            ui,
+                            &hover_curves,
+                            transform.inverse_x(pointer.x).max(0.0),
+                            plot_accel_args.dpi,
+                        );
+                    });
+                }
+            }
+        });
 
-                                    add_cap_type(self, ui);
-                                    ui.end_row();
+        let snapshot = capture_snapshot(self);
+        self.history.record(
+            &snapshot,
+            //a slider drag or sweep animation is one edit like typing in a field
+            ctx.memory(|memory| memory.focused())
+                .or_else(|| ctx.dragged_id())
+                .or_else(|| self.sweep.playing.then(|| egui::Id::new("sweep_scrubber"))),
+        );
+    }
+}
 
-                                    add_cap_input(self, ui);
-                                    ui.end_row();
-                                }
-                                rawaccel_convert::types::CapMode::Output => {
-                                    add_acceleration(self, ui);
-                                    ui.end_row();
+//the settings that shape the curve, theme, game and number format changes are not part of the edit history
+fn curve_settings_strings() -> Vec<fn(&mut RawaccelConvertSettings) -> &mut String> {
+    let mut settings_strings: Vec<fn(&mut RawaccelConvertSettings) -> &mut String> = vec![
+        |settings| &mut settings.point_count_string,
+        |settings| &mut settings.dpi_string,
+        |settings| &mut settings.curve_type_string,
+        |settings| &mut settings.lookup_table_string,
+    ];
+    for parameter in parameters::all_parameters() {
+        settings_strings.push(parameter.settings_string);
+    }
+    return settings_strings;
+}
 
-                                    add_cap_type(self, ui);
-                                    ui.end_row();
+fn capture_snapshot(rawaccel_convert_gui: &RawaccelConvertGui) -> EditSnapshot {
+    let mut settings = rawaccel_convert_gui.settings.clone();
+    return EditSnapshot {
+        settings_strings: curve_settings_strings()
+            .into_iter()
+            .map(|settings_string| settings_string(&mut settings).clone())
+            .collect(),
+        mode: rawaccel_convert_gui.accel_args.mode.clone(),
+        cap_mode: rawaccel_convert_gui.accel_args.cap_mode.clone(),
+        gain: rawaccel_convert_gui.accel_args.gain,
+    };
+}
 
-                                    add_cap_output(self, ui);
-                                    ui.end_row();
-                                }
-                            }
+fn restore_snapshot(rawaccel_convert_gui: &mut RawaccelConvertGui, snapshot: EditSnapshot) {
+    for (settings_string, value) in curve_settings_strings()
+        .into_iter()
+        .zip(snapshot.settings_strings)
+    {
+        *settings_string(&mut rawaccel_convert_gui.settings) = value;
+    }
+    rawaccel_convert_gui.accel_args.mode = snapshot.mode;
+    rawaccel_convert_gui.accel_args.cap_mode = snapshot.cap_mode;
+    rawaccel_convert_gui.accel_args.gain = snapshot.gain;
+}
 
-                            add_input_offset(self, ui);
-                            ui.end_row();
+fn undo(rawaccel_convert_gui: &mut RawaccelConvertGui) {
+    let snapshot = capture_snapshot(rawaccel_convert_gui);
+    if let Some(restored) = rawaccel_convert_gui.history.undo(&snapshot) {
+        restore_snapshot(rawaccel_convert_gui, restored);
+    }
+}
 
-                            add_power_classic(self, ui);
-                            ui.end_row();
-                        }
-                        AccelMode::Jump => {
-                            add_gain(self, ui);
-                            ui.end_row();
-
-                            add_smooth(self, ui);
-                            ui.end_row();
-
-                            add_input(self, ui);
-                            ui.end_row();
-
-                            add_output(self, ui);
-                            ui.end_row();
-                        }
-                        AccelMode::Natural => {
-                            add_gain(self, ui);
-                            ui.end_row();
-
-                            add_decay(self, ui);
-                            ui.end_row();
-
-                            add_input_offset(self, ui);
-                            ui.end_row();
-
-                            add_limit(self, ui);
-                            ui.end_row();
-                        }
-                        AccelMode::Synchronous => {
-                            add_gain(self, ui);
-                            ui.end_row();
-
-                            add_gamma(self, ui);
-                            ui.end_row();
-
-                            add_smooth(self, ui);
-                            ui.end_row();
-
-                            add_motivity(self, ui);
-                            ui.end_row();
-
-                            add_syncspeed(self, ui);
-                            ui.end_row();
-                        }
-                        AccelMode::Motivity => {
-                            add_gain(self, ui);
-                            ui.end_row();
-
-                            add_growthrate(self, ui);
-                            ui.end_row();
-
-                            add_motivity(self, ui);
-                            ui.end_row();
+fn redo(rawaccel_convert_gui: &mut RawaccelConvertGui) {
+    let snapshot = capture_snapshot(rawaccel_convert_gui);
+    if let Some(restored) = rawaccel_convert_gui.history.redo(&snapshot) {
+        restore_snapshot(rawaccel_convert_gui, restored);
+    }
+}
 
-                            add_midpoint(self, ui);
-                            ui.end_row();
-                        }
-                        AccelMode::Power => {
-                            add_gain(self, ui);
-                            ui.end_row();
+//points where the curve can't be evaluated are left out
+fn sample_curve(args: &AccelArgs, min_x: f64, max_x: f64, count: usize) -> Vec<[f64; 2]> {
+    let mut points = vec![];
+    for i in 0..=count {
+        let x = min_x + (max_x - min_x) * i as f64 / count as f64;
+        if let Ok(y) = try_get_point(x, args) {
+            points.push([x, y]);
+        }
+    }
+    return points;
+}
 
-                            match self.accel_args.cap_mode {
-                                rawaccel_convert::types::CapMode::InputOutput => {
-                                    add_cap_type(self, ui);
-                                    ui.end_row();
+//plotted curves only change on edits, the overlays add a few more next to the current curve
+const MAX_CACHED_PLOT_SAMPLES: usize = 16;
 
-                                    add_cap_input(self, ui);
-                                    ui.end_row();
+struct CachedPlotSamples {
+    args: AccelArgs,
+    bounds: [f64; 2],
+    transform: PlotTransform,
+    points: Vec<[f64; 2]>,
+}
 
-                                    add_cap_output(self, ui);
-                                    ui.end_row();
-                                }
-                                rawaccel_convert::types::CapMode::Input => {
-                                    add_scale(self, ui);
-                                    ui.end_row();
+//`plot_samples` reusing the points from earlier frames, most recently used first
+fn cached_plot_samples(
+    cache: &mut Vec<CachedPlotSamples>,
+    args: &AccelArgs,
+    min_x: f64,
+    max_x: f64,
+    transform: &PlotTransform,
+) -> Vec<[f64; 2]> {
+    let position = cache.iter().position(|cached| {
+        cached.args == *args && cached.bounds == [min_x, max_x] && cached.transform == *transform
+    });
+    let cached = match position {
+        Some(position) => cache.remove(position),
+        None => CachedPlotSamples {
+            args: args.clone(),
+            bounds: [min_x, max_x],
+            transform: *transform,
+            points: plot_samples(args, min_x, max_x, transform),
+        },
+    };
+    let points = cached.points.clone();
+    cache.insert(0, cached);
+    cache.truncate(MAX_CACHED_PLOT_SAMPLES);
+    return points;
+}
 
-                                    add_cap_type(self, ui);
-                                    ui.end_row();
+//plot points for `args` independent of the export point count, denser where the plotted curve bends
+fn plot_samples(
+    args: &AccelArgs,
+    min_x: f64,
+    max_x: f64,
+    transform: &PlotTransform,
+) -> Vec<[f64; 2]> {
+    let min_x = transform.min_speed(min_x, max_x);
+    return curve_analysis::adaptive_samples(|t| {
+        //even steps on the plotted axis
+        let x = match transform.units.log_x {
+            true => min_x * (max_x / min_x).powf(t),
+            false => min_x + (max_x - min_x) * t,
+        };
+        let y = try_get_point(x, args).ok()?;
+        Some(transform.point(x, y))
+    });
+}
 
-                                    add_cap_input(self, ui);
-                                    ui.end_row();
-                                }
-                                rawaccel_convert::types::CapMode::Output => {
-                                    add_scale(self, ui);
-                                    ui.end_row();
+fn plot_transform(rawaccel_convert_gui: &RawaccelConvertGui) -> PlotTransform {
+    return PlotTransform {
+        units: rawaccel_convert_gui.plot_units,
+        dpi: rawaccel_convert_gui.accel_args.dpi,
+        yaw: rawaccel_convert_gui.game_yaw,
+        game_sens: rawaccel_convert_gui.game_sens,
+    };
+}
 
-                                    add_cap_type(self, ui);
-                                    ui.end_row();
+fn transform_points(points: Vec<[f64; 2]>, transform: &PlotTransform) -> Vec<[f64; 2]> {
+    return points
+        .into_iter()
+        .map(|[x, y]| transform.point(x, y))
+        //zero has no place on a log axis
+        .filter(|[x, y]| x.is_finite() && y.is_finite())
+        .collect();
+}
 
-                                    add_cap_output(self, ui);
-                                    ui.end_row();
-                                }
-                            }
+fn transform_bounds(
+    bounds: ([f64; 2], [f64; 2]),
+    transformed_points: &[[f64; 2]],
+    transform: &PlotTransform,
+) -> ([f64; 2], [f64; 2]) {
+    let min_x = transform.x(transform.min_speed(bounds.0[0], bounds.1[0]));
+    let max_x = transform.x(bounds.1[0]);
+    match transform.units.output {
+        OutputUnit::Sens | OutputUnit::Velocity if !transform.units.log_y => {
+            return ([min_x, bounds.0[1]], [max_x, bounds.1[1]]);
+        }
+        _ if transform.units.log_y => {
+            let visible_y = transformed_points
+                .iter()
+                .filter(|[x, _]| *x >= min_x && *x <= max_x)
+                .map(|[_, y]| *y);
+            let min_y = visible_y.clone().fold(f64::MAX, f64::min);
+            let max_y = visible_y.fold(f64::MIN, f64::max);
+            if min_y > max_y {
+                return ([min_x, -1.0], [max_x, 1.0]);
+            }
+            //a tenth of a decade around flat curves
+            let padding = ((max_y - min_y) * 0.05).max(0.1);
+            return ([min_x, min_y - padding], [max_x, max_y + padding]);
+        }
+        _ => {
+            //cm/360 goes to infinity as sens approaches zero so use the visible points instead
+            let mut min_y = f64::MAX;
+            let mut max_y: f64 = 0.0;
+            for [x, y] in transformed_points {
+                if *x < min_x || *x > max_x || !y.is_finite() {
+                    continue;
+                }
+                min_y = min_y.min(*y);
+                max_y = max_y.max(*y);
+            }
+            if min_y > max_y {
+                return ([min_x, 0.0], [max_x, 1.0]);
+            }
+            return ([min_x, min_y * 0.9], [max_x, max_y * 1.1]);
+        }
+    }
+}
 
-                            add_exponent(self, ui);
-                            ui.end_row();
+fn format_plot_coordinates(x: f64, y: f64, transform: &PlotTransform) -> String {
+    let secondary_input = transform.secondary_input();
+    let mut text = format!(
+        "{:.2} {} ({:.2} {})",
+        transform.axis_x(x),
+        transform.units.input.label(),
+        secondary_input.speed_from_counts_per_ms(transform.inverse_x(x), transform.dpi),
+        secondary_input.label(),
+    );
+    text += &format!(
+        "\n{:.3} {}",
+        transform.axis_y(y),
+        transform.units.output.label()
+    );
+    if let (Some(secondary_y), Some(label)) =
+        (transform.secondary_y(y), transform.secondary_y_label())
+    {
+        text += &format!(" ({:.3} {})", secondary_y, label);
+    }
+    return text;
+}
 
-                            add_output_offset(self, ui);
-                            ui.end_row();
-                        }
-                        AccelMode::Lookup => {
-                            add_lookup_table_box(self, ui);
-                            ui.end_row();
+fn plot_x_axes(transform: &PlotTransform) -> Vec<egui_plot::AxisHints<'static>> {
+    let transform = *transform;
+    let secondary_input = transform.secondary_input();
+    let mut primary = egui_plot::AxisHints::new_x()
+        .label(format!("Input Speed ({})", transform.units.input.label()));
+    if transform.units.log_x {
+        primary =
+            primary.formatter(move |mark, _range| format_log_mark(transform.axis_x(mark.value)));
+    }
+    return vec![
+        primary,
+        egui_plot::AxisHints::new_x()
+            .label(secondary_input.label())
+            .placement(egui_plot::VPlacement::Top)
+            .formatter(move |mark, _range| {
+                let speed = transform.inverse_x(mark.value);
+                format!(
+                    "{:.1}",
+                    secondary_input.speed_from_counts_per_ms(speed, transform.dpi)
+                )
+            }),
+    ];
+}
 
-                            add_apply_as(self, ui);
-                            ui.end_row();
-                        }
-                        AccelMode::Noaccel => {}
-                    }
-                });
-                add_points_dump(self, ui);
-            });
+fn plot_y_axes(transform: &PlotTransform) -> Vec<egui_plot::AxisHints<'static>> {
+    let transform = *transform;
+    let mut primary = egui_plot::AxisHints::new_y().label(transform.units.output.label());
+    if transform.units.log_y {
+        primary =
+            primary.formatter(move |mark, _range| format_log_mark(transform.axis_y(mark.value)));
+    }
+    let mut axes = vec![primary];
+    if let Some(label) = transform.secondary_y_label() {
+        axes.push(
+            egui_plot::AxisHints::new_y()
+                .label(label)
+                .placement(egui_plot::HPlacement::Right)
+                .formatter(
+                    move |mark, _range| match transform.secondary_y(mark.value) {
+                        Some(some) if some.is_finite() => format!("{:.2}", some),
+                        _ => String::default(),
+                    },
+                ),
+        );
+    }
+    return axes;
+}
 
-        egui::CentralPanel::default().show(ctx, |ui| {
-            let plot_accel_args = self.accel_args.clone();
-            let plot_points = self.curvegen.points.clone();
-            let plot_bounds = get_bounds(self, &plot_accel_args);
-            let mut plot = egui_plot::Plot::new("lines_demo")
-                .legend(egui_plot::Legend::default())
-                .show_axes(true)
-                .show_grid(true);
-            plot = plot
-                .coordinates_formatter(
-                    egui_plot::Corner::LeftBottom,
-                    egui_plot::CoordinatesFormatter::default(),
-                )
-                .allow_zoom(false)
-                .allow_drag(false)
-                .allow_scroll(false);
-            plot.show(ui, |plot_ui| {
-                plot_ui.set_plot_bounds(egui_plot::PlotBounds::from_min_max(
-                    plot_bounds.0,
-                    plot_bounds.1,
-                ));
-                plot_ui.line(
-                    egui_plot::Line::new(egui_plot::PlotPoints::new(convert_points(plot_points)))
-                        .color(egui::Color32::from_rgb(100, 100, 200))
-                        .style(egui_plot::LineStyle::Solid),
-                );
-            })
-            .response
+//grid lines on a log axis at each power of ten, with fainter ones at 2x through 9x in between
+fn log_grid_spacer(input: egui_plot::GridInput) -> Vec<egui_plot::GridMark> {
+    let (min, max) = input.bounds;
+    if !min.is_finite() || !max.is_finite() || max - min > 100.0 {
+        return vec![];
+    }
+    let mut marks = vec![];
+    for decade in (min.floor() as i32)..=(max.ceil() as i32) {
+        marks.push(egui_plot::GridMark {
+            value: decade as f64,
+            step_size: 1.0,
         });
+        for multiple in 2..10 {
+            marks.push(egui_plot::GridMark {
+                value: decade as f64 + (multiple as f64).log10(),
+                step_size: 0.1,
+            });
+        }
     }
+    return marks;
 }
 
-fn convert_points(points: Vec<rawaccel_convert::types::Point>) -> Vec<[f64; 2]> {
-    let mut egui_points = vec![];
-    for point in points {
-        egui_points.push([point.x, point.y]);
-    }
-    return egui_points;
+//log axes span several magnitudes so fixed decimals would hide the small end
+fn format_log_mark(value: f64) -> String {
+    return fit::format_parameter(format!("{:.2e}", value).parse().unwrap_or(value));
 }
 
-fn get_point(x: f64, args: &AccelArgs) -> f64 {
-    let y = args.sens_multiplier
-        * match &args.mode {
-            AccelMode::Linear => rawaccel_convert::accel_curves::classic::classic(x, &args),
-            AccelMode::Classic => rawaccel_convert::accel_curves::classic::classic(x, &args),
-            AccelMode::Jump => rawaccel_convert::accel_curves::jump::jump(x, &args),
-            AccelMode::Natural => rawaccel_convert::accel_curves::natural::natural(x, &args),
-            AccelMode::Synchronous => {
-                rawaccel_convert::accel_curves::synchronous::synchronous(x, &args)
-                    .unwrap_or_default()
-            }
-            AccelMode::Power => rawaccel_convert::accel_curves::power::power(x, &args),
-            AccelMode::Motivity => {
-                rawaccel_convert::accel_curves::motivity::motivity(x, args).unwrap_or_default()
-            }
-            AccelMode::Lookup => {
-                rawaccel_convert::accel_curves::lookup::lookup(x, args).unwrap_or_default()
+//failures become NaN so they can't pass for a real value, use `try_get_point` to get the reason
+pub fn get_point(x: f64, args: &AccelArgs) -> f64 {
+    return try_get_point(x, args).unwrap_or(f64::NAN);
+}
+
+pub fn try_get_point(x: f64, args: &AccelArgs) -> Result<f64, String> {
+    let sens = match &args.mode {
+        AccelMode::Linear => Some(rawaccel_convert::accel_curves::classic::classic(x, &args)),
+        AccelMode::Classic => Some(rawaccel_convert::accel_curves::classic::classic(x, &args)),
+        AccelMode::Jump => Some(rawaccel_convert::accel_curves::jump::jump(x, &args)),
+        AccelMode::Natural => Some(rawaccel_convert::accel_curves::natural::natural(x, &args)),
+        AccelMode::Synchronous => {
+            rawaccel_convert::accel_curves::synchronous::synchronous(x, &args)
+        }
+        AccelMode::Power => Some(rawaccel_convert::accel_curves::power::power(x, &args)),
+        AccelMode::Motivity => rawaccel_convert::accel_curves::motivity::motivity(x, args),
+        AccelMode::Lookup => {
+            if args.lookup_data.is_empty() {
+                return Err("Look up table has no points".to_string());
             }
-            AccelMode::Noaccel => rawaccel_convert::accel_curves::noaccel::noaccel(x, &args),
-        };
-    match args.point_scaling {
-        PointScaling::Sens => return y,
+            rawaccel_convert::accel_curves::lookup::lookup(x, args)
+        }
+        AccelMode::Noaccel => Some(rawaccel_convert::accel_curves::noaccel::noaccel(x, &args)),
+    };
+    let y = match sens {
+        Some(some) => args.sens_multiplier * some,
+        None => {
+            return Err(format!(
+                "{} curve could not be evaluated at {} counts/ms",
+                fit::mode_label(&args.mode, args.gain),
+                x
+            ))
+        }
+    };
+    let y = match args.point_scaling {
+        PointScaling::Sens => y,
         PointScaling::Velocity
         | PointScaling::Libinput
         | PointScaling::LibinputDebug
@@ -451,18 +1175,88 @@ fn get_point(x: f64, args: &AccelArgs) -> f64 {
             let converted_point = rawaccel_convert::convert_curve::sensitivity_point_to_velocity(
                 rawaccel_convert::types::Point { x, y },
             );
-            return converted_point.y;
+            converted_point.y
         }
         PointScaling::Gain => {
-            unimplemented!()
+            return Err("Gain scaling is not supported".to_string());
+        }
+    };
+    if !y.is_finite() {
+        return Err(format!(
+            "{} curve is not finite at {} counts/ms",
+            fit::mode_label(&args.mode, args.gain),
+            x
+        ));
+    }
+    return Ok(y);
+}
+
+//evaluates the curve across the plotted speeds so failures show up as a message instead of a broken plot
+pub fn check_curve(args: &AccelArgs, curvegen: &CurvegenResult) -> Result<(), String> {
+    check_points(&curvegen.points)?;
+    let max_x = (args.dpi / 20) as f64;
+    for i in 0..=64 {
+        try_get_point(0.1 + (max_x - 0.1) * i as f64 / 64.0, args)?;
+    }
+    return Ok(());
+}
+
+fn check_points(points: &[rawaccel_convert::types::Point]) -> Result<(), String> {
+    if points.is_empty() {
+        return Err("Curve generated no points".to_string());
+    }
+    if let Some(point) = points
+        .iter()
+        .find(|point| !point.x.is_finite() || !point.y.is_finite())
+    {
+        return Err(format!("Curve is not finite at {} counts/ms", point.x));
+    }
+    return Ok(());
+}
+
+//input speed where the cap starts applying, only classic style modes have a cap
+pub fn cap_speed(args: &AccelArgs) -> Option<f64> {
+    if !parameters::has_cap(&args.mode) {
+        return None;
+    }
+    match args.cap_mode {
+        CapMode::Input | CapMode::InputOutput => return Some(args.cap.x),
+        CapMode::Output => {}
+    }
+
+    //output caps limit sens or gain, search for the speed where it gets there
+    let sens_args = sens_args(args);
+    let capped_value = |x: f64| -> f64 {
+        let value = match args.gain {
+            true => {
+                let step = 1e-4;
+                ((x + step) * get_point(x + step, &sens_args) - x * get_point(x, &sens_args)) / step
+            }
+            false => get_point(x, &sens_args),
+        };
+        return value / args.sens_multiplier;
+    };
+    let target = args.cap.y * (1.0 - 1e-6);
+    let mut low = 0.0;
+    let mut high = 1000.0;
+    if capped_value(low) >= target || capped_value(high) < target {
+        return None;
+    }
+    for _ in 0..60 {
+        let middle = (low + high) / 2.0;
+        if capped_value(middle) >= target {
+            high = middle;
+        } else {
+            low = middle;
         }
     }
+    return Some(high);
 }
 
 fn get_bounds(
     rawaccel_convert_gui: &mut RawaccelConvertGui,
     args: &AccelArgs,
-) -> ([f64; 2], [f64; 2]) {
+) -> Result<([f64; 2], [f64; 2]), String> {
     match args.mode {
         AccelMode::Lookup => {
             let plot_min_x = 0.1;
@@ -481,10 +1275,16 @@ fn get_bounds(
                     plot_max_y = point.y;
                 }
             }
-            return (
+            if plot_min_y > plot_max_y {
+                return Err(format!(
+                    "Look up table has no points between {} and {} counts/ms",
+                    plot_min_x, plot_max_x
+                ));
+            }
+            return Ok((
                 [plot_min_x, plot_min_y * 0.9],
                 [plot_max_x, plot_max_y * 1.1],
-            );
+            ));
         }
         _ => {
             let plot_min_x = match args.mode {
@@ -492,17 +1292,22 @@ fn get_bounds(
                 _ => 0.0,
             };
             let plot_max_x = (args.dpi.clone() / 20) as f64;
-            return (
-                [plot_min_x, get_point(plot_min_x, args) * 0.9],
-                [plot_max_x, get_point(plot_max_x, args) * 1.1],
-            );
+            return Ok((
+                [plot_min_x, try_get_point(plot_min_x, args)? * 0.9],
+                [plot_max_x, try_get_point(plot_max_x, args)? * 1.1],
+            ));
         }
     }
 }
 
+//used while the curve is invalid so the plot still has a sensible range
+fn fallback_bounds(args: &AccelArgs) -> ([f64; 2], [f64; 2]) {
+    return ([0.0, 0.0], [(args.dpi / 20).max(1) as f64, 2.0]);
+}
+
 fn add_dpi(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
     let mut color = ui.visuals().text_color();
-    match rawaccel_convert_gui.settings.dpi_string.parse::<u32>() {
+    match expression::evaluate_whole(&rawaccel_convert_gui.settings.dpi_string) {
         Ok(ok) => rawaccel_convert_gui.accel_args.dpi = ok,
         Err(_) => {
             color = ui.visuals().error_fg_color;
@@ -514,30 +1319,79 @@ fn add_dpi(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
     );
     ui.add_sized(
         ui.available_size(),
-        egui::TextEdit::singleline(&mut rawaccel_convert_gui.settings.dpi_string),
+        egui::TextEdit::singleline(&mut rawaccel_convert_gui.settings.dpi_string)
+            .id(validation::field_id("dpi")),
+    );
+    add_evaluated_value(ui, &rawaccel_convert_gui.settings.dpi_string);
+}
+
+fn add_game_preset(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
+    ui.add_sized(
+        ui.available_size(),
+        egui::Label::new("Game").selectable(false),
+    );
+    let settings = &mut rawaccel_convert_gui.settings;
+    ui.push_id("game_preset_dropdown", |ui| {
+        egui::ComboBox::from_label("")
+            .selected_text(settings.game_name.clone())
+            .show_ui(ui, |ui| {
+                let presets = games::GAME_PRESETS
+                    .iter()
+                    .map(|preset| (preset.name.to_string(), preset.yaw));
+                let custom_games = settings
+                    .custom_games
+                    .iter()
+                    .map(|game| (game.name.clone(), game.yaw))
+                    .collect::<Vec<_>>();
+                for (name, yaw) in presets.chain(custom_games) {
+                    if ui
+                        .selectable_label(settings.game_name == name, &name)
+                        .clicked()
+                    {
+                        settings.game_yaw_string = fit::format_parameter(yaw);
+                        settings.game_name = name;
+                    }
+                }
+            });
+    });
+}
+
+fn add_game_yaw(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
+    let mut color = ui.visuals().text_color();
+    match expression::evaluate(&rawaccel_convert_gui.settings.game_yaw_string) {
+        Ok(ok) if ok > 0.0 => rawaccel_convert_gui.game_yaw = ok,
+        _ => {
+            color = ui.visuals().error_fg_color;
+        }
+    }
+    ui.add_sized(
+        ui.available_size(),
+        egui::Label::new(egui::RichText::new("Game Yaw").color(color)).selectable(false),
+    );
+    ui.add_sized(
+        ui.available_size(),
+        egui::TextEdit::singleline(&mut rawaccel_convert_gui.settings.game_yaw_string),
     );
+    add_evaluated_value(ui, &rawaccel_convert_gui.settings.game_yaw_string);
 }
 
-fn add_sens_multiplier(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
+fn add_game_sens(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
     let mut color = ui.visuals().text_color();
-    match rawaccel_convert_gui
-        .settings
-        .sens_multiplier_string
-        .parse::<f64>()
-    {
-        Ok(ok) => rawaccel_convert_gui.accel_args.sens_multiplier = ok,
-        Err(_) => {
+    match expression::evaluate(&rawaccel_convert_gui.settings.game_sens_string) {
+        Ok(ok) if ok > 0.0 => rawaccel_convert_gui.game_sens = ok,
+        _ => {
             color = ui.visuals().error_fg_color;
         }
     }
     ui.add_sized(
         ui.available_size(),
-        egui::Label::new(egui::RichText::new("Sens Multiplier").color(color)).selectable(false),
+        egui::Label::new(egui::RichText::new("Game Sens").color(color)).selectable(false),
     );
     ui.add_sized(
         ui.available_size(),
-        egui::TextEdit::singleline(&mut rawaccel_convert_gui.settings.sens_multiplier_string),
+        egui::TextEdit::singleline(&mut rawaccel_convert_gui.settings.game_sens_string),
     );
+    add_evaluated_value(ui, &rawaccel_convert_gui.settings.game_sens_string);
 }
 
 fn add_curve_type(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
@@ -612,28 +1466,6 @@ fn add_gain(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
     ui.checkbox(&mut rawaccel_convert_gui.accel_args.gain, "");
 }
 
-fn add_acceleration(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
-    let mut color = ui.visuals().text_color();
-    match rawaccel_convert_gui
-        .settings
-        .acceleration_string
-        .parse::<f64>()
-    {
-        Ok(ok) => rawaccel_convert_gui.accel_args.acceleration = ok,
-        Err(_) => {
-            color = ui.visuals().error_fg_color;
-        }
-    }
-    ui.add_sized(
-        ui.available_size(),
-        egui::Label::new(egui::RichText::new("Acceleration").color(color)).selectable(false),
-    );
-    ui.add_sized(
-        ui.available_size(),
-        egui::TextEdit::singleline(&mut rawaccel_convert_gui.settings.acceleration_string),
-    );
-}
-
 fn add_cap_type(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
     ui.add_sized(
         ui.available_size(),
@@ -667,369 +1499,161 @@ fn add_cap_type(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui
     });
 }
 
-fn add_cap_input(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
+fn add_parameter(
+    rawaccel_convert_gui: &mut RawaccelConvertGui,
+    ui: &mut egui::Ui,
+    parameter: &Parameter,
+) {
     let mut color = ui.visuals().text_color();
-    match rawaccel_convert_gui
-        .settings
-        .cap_input_string
-        .parse::<f64>()
-    {
-        Ok(ok) => rawaccel_convert_gui.accel_args.cap.x = ok,
+    match expression::evaluate((parameter.settings_string)(
+        &mut rawaccel_convert_gui.settings,
+    )) {
+        Ok(ok) => (parameter.set)(&mut rawaccel_convert_gui.accel_args, ok),
         Err(_) => {
             color = ui.visuals().error_fg_color;
         }
     }
     ui.add_sized(
         ui.available_size(),
-        egui::Label::new(egui::RichText::new("Cap: Input").color(color)).selectable(false),
-    );
+        egui::Label::new(egui::RichText::new(parameter.label).color(color)).selectable(false),
+    )
+    .on_hover_text(parameter.help);
     ui.add_sized(
         ui.available_size(),
-        egui::TextEdit::singleline(&mut rawaccel_convert_gui.settings.cap_input_string),
+        egui::TextEdit::singleline((parameter.settings_string)(
+            &mut rawaccel_convert_gui.settings,
+        ))
+        .id(validation::field_id(parameter.id)),
     );
+    add_evaluated_value(
+        ui,
+        (parameter.settings_string)(&mut rawaccel_convert_gui.settings),
+    );
+    if rawaccel_convert_gui.parameter_sliders {
+        ui.end_row();
+        ui.label("");
+        add_parameter_slider(rawaccel_convert_gui, ui, parameter);
+    }
 }
 
-fn add_cap_output(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
-    let mut color = ui.visuals().text_color();
-    match rawaccel_convert_gui
-        .settings
-        .cap_output_string
-        .parse::<f64>()
-    {
-        Ok(ok) => rawaccel_convert_gui.accel_args.cap.y = ok,
-        Err(_) => {
-            color = ui.visuals().error_fg_color;
-        }
+const COARSE_SLIDER_STEPS: u32 = 20;
+
+//writes back through the settings string so typed values and the slider stay in sync
+//shift drags ten times finer, ctrl/cmd snaps to one of `COARSE_SLIDER_STEPS` steps across the range
+fn add_parameter_slider(
+    rawaccel_convert_gui: &mut RawaccelConvertGui,
+    ui: &mut egui::Ui,
+    parameter: &Parameter,
+) {
+    let (min, max) = (parameter.slider_range)(&rawaccel_convert_gui.accel_args);
+    let logarithmic = parameter.logarithmic && min > 0.0;
+    let previous = match expression::evaluate((parameter.settings_string)(
+        &mut rawaccel_convert_gui.settings,
+    )) {
+        Ok(ok) => ok,
+        _ => (parameter.get)(&rawaccel_convert_gui.accel_args),
+    }
+    .clamp(min, max);
+    let mut value = previous;
+    let response = ui
+        .scope(|ui| {
+            ui.spacing_mut().slider_width = ui.available_width();
+            ui.add(
+                egui::Slider::new(&mut value, min..=max)
+                    .show_value(false)
+                    .logarithmic(logarithmic),
+            )
+        })
+        .inner
+        .on_hover_text(format!(
+            "Shift: fine\nCtrl: coarse, in {COARSE_SLIDER_STEPS} steps"
+        ));
+    if !response.changed() {
+        return;
     }
-    ui.add_sized(
-        ui.available_size(),
-        egui::Label::new(egui::RichText::new("Cap: Output").color(color)).selectable(false),
-    );
-    ui.add_sized(
-        ui.available_size(),
-        egui::TextEdit::singleline(&mut rawaccel_convert_gui.settings.cap_output_string),
-    );
-}
 
-fn add_input_offset(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
-    let mut color = ui.visuals().text_color();
-    match rawaccel_convert_gui
-        .settings
-        .input_offset_string
-        .parse::<f64>()
-    {
-        Ok(ok) => rawaccel_convert_gui.accel_args.input_offset = ok,
-        Err(_) => {
-            color = ui.visuals().error_fg_color;
+    let modifiers = ui.input(|input| input.modifiers);
+    if modifiers.shift {
+        let fraction = (response.drag_delta().x / response.rect.width()) as f64 * 0.1;
+        value = match logarithmic {
+            true => previous * (max / min).powf(fraction),
+            false => previous + (max - min) * fraction,
         }
+        .clamp(min, max);
     }
-    ui.add_sized(
-        ui.available_size(),
-        egui::Label::new(egui::RichText::new("Input Offset").color(color)).selectable(false),
-    );
-    ui.add_sized(
-        ui.available_size(),
-        egui::TextEdit::singleline(&mut rawaccel_convert_gui.settings.input_offset_string),
-    );
-}
-
-fn add_power_classic(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
-    let mut color = ui.visuals().text_color();
-    match rawaccel_convert_gui
-        .settings
-        .exponent_classic_string
-        .parse::<f64>()
-    {
-        Ok(ok) => rawaccel_convert_gui.accel_args.exponent_classic = ok,
-        Err(_) => {
-            color = ui.visuals().error_fg_color;
+    if modifiers.command {
+        //snaps to one of COARSE_SLIDER_STEPS steps across the slider, evenly spaced on its scale
+        let steps = COARSE_SLIDER_STEPS as f64;
+        value = match logarithmic {
+            true => {
+                min * (max / min)
+                    .powf(((value / min).ln() / (max / min).ln() * steps).round() / steps)
+            }
+            false => min + (max - min) * ((value - min) / (max - min) * steps).round() / steps,
+        };
+        if value != 0.0 {
+            let magnitude = 10f64.powf(value.abs().log10().floor() - 1.0);
+            value = (value / magnitude).round() * magnitude;
         }
+        value = value.clamp(min, max);
     }
-    ui.add_sized(
-        ui.available_size(),
-        egui::Label::new(egui::RichText::new("Power").color(color)).selectable(false),
-    );
-    ui.add_sized(
-        ui.available_size(),
-        egui::TextEdit::singleline(&mut rawaccel_convert_gui.settings.exponent_classic_string),
-    );
+
+    apply_parameter_value(rawaccel_convert_gui, parameter, value);
 }
 
-fn add_smooth(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
-    let mut color = ui.visuals().text_color();
-    match rawaccel_convert_gui.settings.smooth_string.parse::<f64>() {
-        Ok(ok) => rawaccel_convert_gui.accel_args.smooth = ok,
-        Err(_) => {
-            color = ui.visuals().error_fg_color;
-        }
+//for controls other than the text field, the text field follows along
+fn apply_parameter_value(
+    rawaccel_convert_gui: &mut RawaccelConvertGui,
+    parameter: &Parameter,
+    value: f64,
+) {
+    *(parameter.settings_string)(&mut rawaccel_convert_gui.settings) = fit::format_parameter(value);
+    (parameter.set)(&mut rawaccel_convert_gui.accel_args, value);
+}
+
+//shows what an expression typed in a field works out to on the row below it
+fn add_evaluated_value(ui: &mut egui::Ui, text: &str) {
+    if !expression::is_expression(text) {
+        return;
     }
-    ui.add_sized(
-        ui.available_size(),
-        egui::Label::new(egui::RichText::new("Smooth").color(color)).selectable(false),
-    );
-    ui.add_sized(
-        ui.available_size(),
-        egui::TextEdit::singleline(&mut rawaccel_convert_gui.settings.smooth_string),
-    );
+    let (text, color) = match expression::evaluate(text) {
+        Ok(ok) => (
+            format!("= {}", fit::format_parameter(ok)),
+            ui.visuals().weak_text_color(),
+        ),
+        Err(err) => (err, ui.visuals().error_fg_color),
+    };
+    ui.end_row();
+    ui.label("");
+    ui.add(egui::Label::new(egui::RichText::new(text).color(color)).selectable(false));
 }
 
-fn add_input(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
+fn add_lookup_table_box(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
     let mut color = ui.visuals().text_color();
-    match rawaccel_convert_gui.settings.input_string.parse::<f64>() {
-        Ok(ok) => rawaccel_convert_gui.accel_args.cap.x = ok,
-        Err(_) => {
+    match rawaccel_convert::args_parser::parse_lookup_table(
+        &rawaccel_convert_gui.settings.lookup_table_string,
+    ) {
+        Some(some) => rawaccel_convert_gui.accel_args.lookup_data = some,
+        None => {
             color = ui.visuals().error_fg_color;
         }
     }
     ui.add_sized(
         ui.available_size(),
-        egui::Label::new(egui::RichText::new("Input").color(color)).selectable(false),
+        egui::Label::new(egui::RichText::new("LUT Points").color(color)).selectable(false),
     );
     ui.add_sized(
         ui.available_size(),
-        egui::TextEdit::singleline(&mut rawaccel_convert_gui.settings.input_string),
+        egui::TextEdit::singleline(&mut rawaccel_convert_gui.settings.lookup_table_string)
+            .id(validation::field_id("lookup_table"))
+            .char_limit(usize::MAX),
     );
 }
 
-fn add_output(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
-    let mut color = ui.visuals().text_color();
-    match rawaccel_convert_gui.settings.output_string.parse::<f64>() {
-        Ok(ok) => rawaccel_convert_gui.accel_args.cap.y = ok,
-        Err(_) => {
-            color = ui.visuals().error_fg_color;
-        }
-    }
-    ui.add_sized(
-        ui.available_size(),
-        egui::Label::new(egui::RichText::new("Output").color(color)).selectable(false),
-    );
+fn add_apply_as(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
     ui.add_sized(
         ui.available_size(),
-        egui::TextEdit::singleline(&mut rawaccel_convert_gui.settings.output_string),
-    );
-}
-
-fn add_decay(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
-    let mut color = ui.visuals().text_color();
-    match rawaccel_convert_gui.settings.decay_string.parse::<f64>() {
-        Ok(ok) => rawaccel_convert_gui.accel_args.decay_rate = ok,
-        Err(_) => {
-            color = ui.visuals().error_fg_color;
-        }
-    }
-    ui.add_sized(
-        ui.available_size(),
-        egui::Label::new(egui::RichText::new("Decay Rate").color(color)).selectable(false),
-    );
-    ui.add_sized(
-        ui.available_size(),
-        egui::TextEdit::singleline(&mut rawaccel_convert_gui.settings.decay_string),
-    );
-}
-
-fn add_limit(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
-    let mut color = ui.visuals().text_color();
-    match rawaccel_convert_gui.settings.limit_string.parse::<f64>() {
-        Ok(ok) => rawaccel_convert_gui.accel_args.limit = ok,
-        Err(_) => {
-            color = ui.visuals().error_fg_color;
-        }
-    }
-    ui.add_sized(
-        ui.available_size(),
-        egui::Label::new(egui::RichText::new("Limit").color(color)).selectable(false),
-    );
-    ui.add_sized(
-        ui.available_size(),
-        egui::TextEdit::singleline(&mut rawaccel_convert_gui.settings.limit_string),
-    );
-}
-
-fn add_gamma(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
-    let mut color = ui.visuals().text_color();
-    match rawaccel_convert_gui.settings.gamma_string.parse::<f64>() {
-        Ok(ok) => rawaccel_convert_gui.accel_args.gamma = ok,
-        Err(_) => {
-            color = ui.visuals().error_fg_color;
-        }
-    }
-    ui.add_sized(
-        ui.available_size(),
-        egui::Label::new(egui::RichText::new("Gamma").color(color)).selectable(false),
-    );
-    ui.add_sized(
-        ui.available_size(),
-        egui::TextEdit::singleline(&mut rawaccel_convert_gui.settings.gamma_string),
-    );
-}
-
-fn add_growthrate(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
-    let mut color = ui.visuals().text_color();
-    match rawaccel_convert_gui.settings.gamma_string.parse::<f64>() {
-        Ok(ok) => rawaccel_convert_gui.accel_args.gamma = ok,
-        Err(_) => {
-            color = ui.visuals().error_fg_color;
-        }
-    }
-    ui.add_sized(
-        ui.available_size(),
-        egui::Label::new(egui::RichText::new("Growth Rate").color(color)).selectable(false),
-    );
-    ui.add_sized(
-        ui.available_size(),
-        egui::TextEdit::singleline(&mut rawaccel_convert_gui.settings.gamma_string),
-    );
-}
-
-fn add_motivity(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
-    let mut color = ui.visuals().text_color();
-    match rawaccel_convert_gui.settings.motivity_string.parse::<f64>() {
-        Ok(ok) => rawaccel_convert_gui.accel_args.motivity = ok,
-        Err(_) => {
-            color = ui.visuals().error_fg_color;
-        }
-    }
-    ui.add_sized(
-        ui.available_size(),
-        egui::Label::new(egui::RichText::new("Motivity").color(color)).selectable(false),
-    );
-    ui.add_sized(
-        ui.available_size(),
-        egui::TextEdit::singleline(&mut rawaccel_convert_gui.settings.motivity_string),
-    );
-}
-
-fn add_syncspeed(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
-    let mut color = ui.visuals().text_color();
-    match rawaccel_convert_gui
-        .settings
-        .syncspeed_string
-        .parse::<f64>()
-    {
-        Ok(ok) => rawaccel_convert_gui.accel_args.sync_speed = ok,
-        Err(_) => {
-            color = ui.visuals().error_fg_color;
-        }
-    }
-    ui.add_sized(
-        ui.available_size(),
-        egui::Label::new(egui::RichText::new("SyncSpeed").color(color)).selectable(false),
-    );
-    ui.add_sized(
-        ui.available_size(),
-        egui::TextEdit::singleline(&mut rawaccel_convert_gui.settings.syncspeed_string),
-    );
-}
-
-fn add_midpoint(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
-    let mut color = ui.visuals().text_color();
-    match rawaccel_convert_gui
-        .settings
-        .syncspeed_string
-        .parse::<f64>()
-    {
-        Ok(ok) => rawaccel_convert_gui.accel_args.sync_speed = ok,
-        Err(_) => {
-            color = ui.visuals().error_fg_color;
-        }
-    }
-    ui.add_sized(
-        ui.available_size(),
-        egui::Label::new(egui::RichText::new("Midpoint").color(color)).selectable(false),
-    );
-    ui.add_sized(
-        ui.available_size(),
-        egui::TextEdit::singleline(&mut rawaccel_convert_gui.settings.syncspeed_string),
-    );
-}
-
-fn add_scale(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
-    let mut color = ui.visuals().text_color();
-    match rawaccel_convert_gui.settings.scale_string.parse::<f64>() {
-        Ok(ok) => rawaccel_convert_gui.accel_args.scale = ok,
-        Err(_) => {
-            color = ui.visuals().error_fg_color;
-        }
-    }
-    ui.add_sized(
-        ui.available_size(),
-        egui::Label::new(egui::RichText::new("Scale").color(color)).selectable(false),
-    );
-    ui.add_sized(
-        ui.available_size(),
-        egui::TextEdit::singleline(&mut rawaccel_convert_gui.settings.scale_string),
-    );
-}
-
-fn add_exponent(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
-    let mut color = ui.visuals().text_color();
-    match rawaccel_convert_gui
-        .settings
-        .exponent_power_string
-        .parse::<f64>()
-    {
-        Ok(ok) => rawaccel_convert_gui.accel_args.exponent_power = ok,
-        Err(_) => {
-            color = ui.visuals().error_fg_color;
-        }
-    }
-    ui.add_sized(
-        ui.available_size(),
-        egui::Label::new(egui::RichText::new("Exponent").color(color)).selectable(false),
-    );
-    ui.add_sized(
-        ui.available_size(),
-        egui::TextEdit::singleline(&mut rawaccel_convert_gui.settings.exponent_power_string),
-    );
-}
-
-fn add_output_offset(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
-    let mut color = ui.visuals().text_color();
-    match rawaccel_convert_gui
-        .settings
-        .output_offset_string
-        .parse::<f64>()
-    {
-        Ok(ok) => rawaccel_convert_gui.accel_args.output_offset = ok,
-        Err(_) => {
-            color = ui.visuals().error_fg_color;
-        }
-    }
-    ui.add_sized(
-        ui.available_size(),
-        egui::Label::new(egui::RichText::new("Output Offset").color(color)).selectable(false),
-    );
-    ui.add_sized(
-        ui.available_size(),
-        egui::TextEdit::singleline(&mut rawaccel_convert_gui.settings.output_offset_string),
-    );
-}
-
-fn add_lookup_table_box(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
-    let mut color = ui.visuals().text_color();
-    match rawaccel_convert::args_parser::parse_lookup_table(
-        &rawaccel_convert_gui.settings.lookup_table_string,
-    ) {
-        Some(some) => rawaccel_convert_gui.accel_args.lookup_data = some,
-        None => {
-            color = ui.visuals().error_fg_color;
-        }
-    }
-    ui.add_sized(
-        ui.available_size(),
-        egui::Label::new(egui::RichText::new("LUT Points").color(color)).selectable(false),
-    );
-    ui.add_sized(
-        ui.available_size(),
-        egui::TextEdit::singleline(&mut rawaccel_convert_gui.settings.lookup_table_string)
-            .char_limit(usize::MAX),
-    );
-}
-
-fn add_apply_as(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
-    ui.add_sized(
-        ui.available_size(),
-        egui::Label::new(egui::RichText::new("Apply As")).selectable(false),
+        egui::Label::new(egui::RichText::new("Apply As")).selectable(false),
     );
     ui.push_id("apply_as_dropdown", |ui| {
         egui::ComboBox::from_label("")
@@ -1046,6 +1670,32 @@ fn add_apply_as(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui
     });
 }
 
+fn add_problems(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
+    if rawaccel_convert_gui.problems.is_empty() {
+        return;
+    }
+    ui.separator();
+    for problem in &rawaccel_convert_gui.problems {
+        let (icon, color) = match problem.severity {
+            validation::Severity::Error => ("❌", ui.visuals().error_fg_color),
+            validation::Severity::Warning => ("⚠", ui.visuals().warn_fg_color),
+        };
+        let clicked = ui
+            .add(
+                egui::Label::new(
+                    egui::RichText::new(format!("{} {}", icon, problem.message)).color(color),
+                )
+                .sense(egui::Sense::click()),
+            )
+            .on_hover_text("Click to edit")
+            .clicked();
+        if clicked {
+            ui.memory_mut(|memory| memory.request_focus(validation::field_id(problem.field)));
+        }
+    }
+    ui.separator();
+}
+
 fn add_points_dump(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
     ui.with_layout(egui::Layout::top_down(egui::Align::Center), |ui| {
         ui.add_sized(
@@ -1056,26 +1706,75 @@ fn add_points_dump(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui:
         let previous_point_scaling = rawaccel_convert_gui.accel_args.point_scaling.clone();
 
         ui.push_id("point_scaling_graph_dropdown", |ui| {
+            egui::ComboBox::from_label("")
+                .width(ui.available_width())
+                .selected_text(rawaccel_convert_gui.plot_units.output.label())
+                .show_ui(ui, |ui| {
+                    for output_unit in [OutputUnit::Sens, OutputUnit::Velocity, OutputUnit::Cm360] {
+                        ui.selectable_value(
+                            &mut rawaccel_convert_gui.plot_units.output,
+                            output_unit,
+                            output_unit.label(),
+                        );
+                    }
+                })
+        });
+        rawaccel_convert_gui.accel_args.point_scaling = match rawaccel_convert_gui.plot_units.output
+        {
+            OutputUnit::Sens | OutputUnit::Cm360 => PointScaling::Sens,
+            OutputUnit::Velocity => PointScaling::Velocity,
+        };
+
+        ui.push_id("input_unit_graph_dropdown", |ui| {
             egui::ComboBox::from_label("")
                 .width(ui.available_width())
                 .selected_text(format!(
-                    "{:?}",
-                    rawaccel_convert_gui.accel_args.point_scaling
+                    "Speed in {}",
+                    rawaccel_convert_gui.plot_units.input.label()
                 ))
                 .show_ui(ui, |ui| {
-                    ui.selectable_value(
-                        &mut rawaccel_convert_gui.accel_args.point_scaling,
-                        PointScaling::Sens,
-                        "Sens",
-                    );
-                    ui.selectable_value(
-                        &mut rawaccel_convert_gui.accel_args.point_scaling,
-                        PointScaling::Velocity,
-                        "Velocity",
-                    );
+                    for input_unit in [
+                        units::InputUnit::CountsPerMs,
+                        units::InputUnit::CmPerS,
+                        units::InputUnit::InPerS,
+                    ] {
+                        ui.selectable_value(
+                            &mut rawaccel_convert_gui.plot_units.input,
+                            input_unit,
+                            input_unit.label(),
+                        );
+                    }
                 })
         });
 
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut rawaccel_convert_gui.plot_units.log_x, "Log Speed");
+            ui.checkbox(&mut rawaccel_convert_gui.plot_units.log_y, "Log Output");
+        });
+
+        let stacked_plots = &mut rawaccel_convert_gui.stacked_plots;
+        ui.checkbox(&mut stacked_plots.enabled, "Stacked Views");
+        if stacked_plots.enabled {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut stacked_plots.sens, "Sens");
+                ui.checkbox(&mut stacked_plots.velocity, "Velocity");
+                ui.checkbox(&mut stacked_plots.gain, "Gain");
+            });
+        }
+
+        //sens graphs show cm/360 on the secondary axis, its game settings are edited in the game window
+        if rawaccel_convert_gui.plot_units.output != OutputUnit::Velocity {
+            ui.horizontal(|ui| {
+                ui.label(format!(
+                    "cm/360 for {}",
+                    rawaccel_convert_gui.settings.game_name
+                ));
+                if ui.button("Game Sensitivity").clicked() {
+                    rawaccel_convert_gui.game_panel.open = true;
+                }
+            });
+        }
+
         ui.add_sized(
             [ui.available_width(), 1.0],
             egui::Label::new("Export Points").selectable(false),
@@ -1121,11 +1820,7 @@ fn add_points_dump(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui:
 
         egui::Grid::new("point_count_grid").show(ui, |ui| {
             let mut color = ui.visuals().text_color();
-            match rawaccel_convert_gui
-                .settings
-                .point_count_string
-                .parse::<u32>()
-            {
+            match expression::evaluate_whole(&rawaccel_convert_gui.settings.point_count_string) {
                 Ok(ok) => match rawaccel_convert_gui.export_point_scaling {
                     PointScaling::Libinput | PointScaling::LibinputDebug => {
                         if ok != 64 {
@@ -1162,7 +1857,8 @@ fn add_points_dump(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui:
                         ui.available_size(),
                         egui::TextEdit::singleline(
                             &mut rawaccel_convert_gui.settings.point_count_string,
-                        ),
+                        )
+                        .id(validation::field_id("point_count")),
                     );
                 }
             };
@@ -1196,14 +1892,72 @@ fn add_points_dump(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui:
             _ => {}
         }
 
-        let generate_points = ui.add_sized(
-            [ui.available_width(), 1.0],
-            egui::Button::new("Generate Points"),
-        );
+        let mut export_blocked = validation::has_errors(&rawaccel_convert_gui.problems);
+        if let Some(curve_error) = &rawaccel_convert_gui.curve_error {
+            ui.colored_label(
+                ui.visuals().error_fg_color,
+                format!("❌ Curve invalid: {}", curve_error),
+            );
+            export_blocked = true;
+        }
+        if let Some(format_problem) = export_format_problem(
+            &rawaccel_convert_gui.settings.number_format,
+            &rawaccel_convert_gui.export_point_scaling,
+        ) {
+            ui.colored_label(
+                ui.visuals().error_fg_color,
+                format!("❌ Number format: {}", format_problem),
+            );
+            export_blocked = true;
+        }
+        if !rawaccel_convert_gui.decreasing_velocity_ranges.is_empty() {
+            let ranges = rawaccel_convert_gui
+                .decreasing_velocity_ranges
+                .iter()
+                .map(|range| format!("{:.2}-{:.2}", range.start, range.end))
+                .collect::<Vec<String>>()
+                .join(", ");
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                format!(
+                    "⚠ Velocity decreases as speed rises at {} counts/ms",
+                    ranges
+                ),
+            );
+            //libinput's custom accel expects a rising velocity curve
+            match rawaccel_convert_gui.export_point_scaling {
+                PointScaling::Libinput | PointScaling::LibinputDebug => {
+                    ui.checkbox(
+                        &mut rawaccel_convert_gui.export_decreasing_velocity,
+                        "Export for libinput anyway",
+                    );
+                    export_blocked |= !rawaccel_convert_gui.export_decreasing_velocity;
+                }
+                _ => {}
+            }
+        }
+
+        let generate_points = ui
+            .add_enabled_ui(!export_blocked, |ui| {
+                ui.add_sized(
+                    [ui.available_width(), 1.0],
+                    egui::Button::new("Generate Points"),
+                )
+                .on_disabled_hover_text("Fix the problems listed above first")
+            })
+            .inner;
+        if let Some(export_error) = &rawaccel_convert_gui.export_error {
+            ui.colored_label(
+                ui.visuals().error_fg_color,
+                format!("❌ Export failed: {}", export_error),
+            );
+        }
         if previous_point_scaling != rawaccel_convert_gui.accel_args.point_scaling
             || rawaccel_convert_gui.export_accel_args_cache != rawaccel_convert_gui.accel_args
         {
             rawaccel_convert_gui.export_accel_args_cache = rawaccel_convert_gui.accel_args.clone();
+            //the libinput override was given for the previous curve only
+            rawaccel_convert_gui.export_decreasing_velocity = false;
 
             rawaccel_convert_gui.accel_args.optimize_curve =
                 match rawaccel_convert_gui.export_point_scaling {
@@ -1218,6 +1972,23 @@ fn add_points_dump(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui:
             //graph curve
             rawaccel_convert_gui.curvegen =
                 rawaccel_convert::generate_curve::generate_curve(&rawaccel_convert_gui.accel_args);
+            rawaccel_convert_gui.curve_error = check_curve(
+                &rawaccel_convert_gui.accel_args,
+                &rawaccel_convert_gui.curvegen,
+            )
+            .err();
+            //the generated points are too coarse with `optimize_curve` to show every dip
+            let max_speed = rawaccel_convert_gui
+                .curvegen
+                .points
+                .last()
+                .map(|point| point.x)
+                .unwrap_or_default();
+            rawaccel_convert_gui.decreasing_velocity_ranges =
+                curve_analysis::decreasing_velocity_ranges(
+                    &curve_analysis::sens_samples(&rawaccel_convert_gui.accel_args, max_speed),
+                    &PointScaling::Sens,
+                );
         }
 
         if generate_points.clicked() {
@@ -1225,41 +1996,2344 @@ fn add_points_dump(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui:
             export_accel_args.point_scaling = rawaccel_convert_gui.export_point_scaling.clone();
             rawaccel_convert_gui.curvegen_export =
                 rawaccel_convert::generate_curve::generate_curve(&export_accel_args);
+            rawaccel_convert_gui.export_error =
+                check_points(&rawaccel_convert_gui.curvegen_export.points).err();
+            let number_format = rawaccel_convert_gui.settings.number_format;
             rawaccel_convert_gui.libinput_steps =
-                rawaccel_convert_gui.curvegen_export.step_size.to_string();
+                number_format.number(rawaccel_convert_gui.curvegen_export.step_size);
             rawaccel_convert_gui.points = match export_accel_args.point_scaling {
+                _ if rawaccel_convert_gui.export_error.is_some() => String::default(),
                 rawaccel_convert::types::PointScaling::Libinput => {
                     let mut output_string = String::default();
                     for point in &rawaccel_convert_gui.curvegen_export.points {
-                        output_string += &(point.y.to_string() + " ");
+                        output_string += &(number_format.number(point.y) + " ");
                     }
                     output_string
                 }
                 rawaccel_convert::types::PointScaling::LookupVelocity | rawaccel_convert::types::PointScaling::LookupSens  => {
                     let mut output_string = String::default();
                     for point in &rawaccel_convert_gui.curvegen_export.points {
-                        output_string += &format!("{},{};\n", point.x, point.y);
+                        output_string +=
+                            &format!("{};\n", number_format.fields(&[point.x, point.y]));
                     }
                     output_string
                 }
-                _ => {
+                _ if number_format == NumberFormat::default() => {
                     format!("{:?}", rawaccel_convert_gui.curvegen_export.points)
                 }
+                _ => {
+                    let mut output_string = String::default();
+                    for point in &rawaccel_convert_gui.curvegen_export.points {
+                        output_string +=
+                            &format!("{}\n", number_format.fields(&[point.x, point.y]));
+                    }
+                    output_string
+                }
             }
         }
     });
 }
 
-fn unselectable_warn_if_debug_build(ui: &mut egui::Ui) {
-    if cfg!(debug_assertions) {
-        ui.add(
-            egui::Label::new(
-                egui::RichText::new("⚠ Debug build ⚠")
-                    .small()
-                    .color(ui.visuals().warn_fg_color),
-            )
-            .selectable(false),
-        );
+//libinput points are one value each so only look up table rows and the plain list can be misread
+fn export_format_problem(
+    number_format: &NumberFormat,
+    point_scaling: &PointScaling,
+) -> Option<String> {
+    match point_scaling {
+        PointScaling::Libinput => return None,
+        PointScaling::LookupVelocity | PointScaling::LookupSens => {
+            return number_format.export_problem(Some(';'))
+        }
+        _ => return number_format.export_problem(None),
+    }
+}
+
+fn add_solver_window(rawaccel_convert_gui: &mut RawaccelConvertGui, ctx: &egui::Context) {
+    let mut open = rawaccel_convert_gui.solver.open;
+    egui::Window::new("Parameter Solver")
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            let parameters = parameters::mode_parameters(&rawaccel_convert_gui.accel_args);
+            if parameters.is_empty() {
+                ui.label("The current curve type has no parameters to solve for.");
+                return;
+            }
+
+            ui.label("Targets");
+            let mut targets = vec![];
+            let mut remove_target = None;
+            egui::Grid::new("solver_targets_grid").show(ui, |ui| {
+                ui.label("Speed (counts/ms)");
+                ui.label("Sens");
+                ui.end_row();
+
+                for (i, (speed_string, sens_string)) in
+                    rawaccel_convert_gui.solver.targets.iter_mut().enumerate()
+                {
+                    let speed = expression::evaluate(speed_string);
+                    let sens = expression::evaluate(sens_string);
+                    for (string, parsed) in
+                        [(&mut *speed_string, &speed), (&mut *sens_string, &sens)]
+                    {
+                        let mut text_edit = egui::TextEdit::singleline(string).desired_width(80.0);
+                        if parsed.is_err() {
+                            text_edit = text_edit.text_color(ui.visuals().error_fg_color);
+                        }
+                        ui.add(text_edit);
+                    }
+                    if ui.button("Remove").clicked() {
+                        remove_target = Some(i);
+                    }
+                    ui.end_row();
+
+                    if let (Ok(x), Ok(y)) = (speed, sens) {
+                        targets.push(rawaccel_convert::types::Point { x, y });
+                    }
+                }
+            });
+            if let Some(i) = remove_target {
+                rawaccel_convert_gui.solver.targets.remove(i);
+                rawaccel_convert_gui.solver.report = None;
+            }
+            if ui.button("Add Target").clicked() {
+                rawaccel_convert_gui
+                    .solver
+                    .targets
+                    .push((String::default(), String::default()));
+            }
+
+            ui.separator();
+            ui.label("Free Parameters");
+            let mut free_parameters = vec![];
+            for parameter in parameters {
+                let toggled = rawaccel_convert_gui
+                    .solver
+                    .toggled_parameters
+                    .contains(&parameter.id);
+                let mut free = parameter.free_by_default != toggled;
+                if ui.checkbox(&mut free, parameter.label).changed() {
+                    match toggled {
+                        true => rawaccel_convert_gui
+                            .solver
+                            .toggled_parameters
+                            .retain(|id| *id != parameter.id),
+                        false => rawaccel_convert_gui
+                            .solver
+                            .toggled_parameters
+                            .push(parameter.id),
+                    }
+                }
+                if free {
+                    free_parameters.push(parameter);
+                }
+            }
+
+            ui.separator();
+            let can_solve = !targets.is_empty()
+                && !free_parameters.is_empty()
+                && targets.len() == rawaccel_convert_gui.solver.targets.len();
+            if ui
+                .add_enabled(can_solve, egui::Button::new("Solve"))
+                .clicked()
+            {
+                let result = fit::fit_parameters(
+                    &rawaccel_convert_gui.accel_args,
+                    &free_parameters,
+                    &targets,
+                );
+                fit::apply_fit(
+                    &mut rawaccel_convert_gui.settings,
+                    &free_parameters,
+                    &result.args,
+                );
+                let achieved = targets
+                    .iter()
+                    .map(|target| rawaccel_convert::types::Point {
+                        x: target.x,
+                        y: get_point(target.x, &sens_args(&result.args)),
+                    })
+                    .collect();
+                rawaccel_convert_gui.solver.report = Some(SolverReport {
+                    rms_error: result.rms_error,
+                    max_error: result.max_error,
+                    achieved,
+                });
+            }
+
+            if let Some(report) = &rawaccel_convert_gui.solver.report {
+                egui::Grid::new("solver_report_grid").show(ui, |ui| {
+                    ui.label("RMS Error");
+                    ui.label(fit::format_parameter(report.rms_error));
+                    ui.end_row();
+
+                    ui.label("Max Error");
+                    ui.label(fit::format_parameter(report.max_error));
+                    ui.end_row();
+
+                    for point in &report.achieved {
+                        ui.label(format!("Sens at {}", point.x));
+                        ui.label(fit::format_parameter(point.y));
+                        ui.end_row();
+                    }
+                });
+            }
+        });
+    rawaccel_convert_gui.solver.open = open;
+}
+
+fn add_curve_fit_window(rawaccel_convert_gui: &mut RawaccelConvertGui, ctx: &egui::Context) {
+    let mut open = rawaccel_convert_gui.curve_fit.open;
+    egui::Window::new("Curve Fit")
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            let curve_fit = &mut rawaccel_convert_gui.curve_fit;
+            ui.horizontal(|ui| {
+                ui.label("Points From");
+                ui.selectable_value(&mut curve_fit.source, FitSource::LookupTable, "LUT Points");
+                ui.selectable_value(&mut curve_fit.source, FitSource::Pasted, "Pasted Points");
+            });
+
+            match curve_fit.source {
+                FitSource::LookupTable => {
+                    curve_fit.points_are_velocity = rawaccel_convert_gui.accel_args.gain;
+                    ui.label("Uses the LUT Points and Apply As setting of the Look Up Table mode.");
+                }
+                FitSource::Pasted => {
+                    ui.label("x,y; pairs, same format as LUT Points");
+                    egui::ScrollArea::vertical()
+                        .max_height(100.0)
+                        .show(ui, |ui| {
+                            ui.add_sized(
+                                [ui.available_width(), 1.0],
+                                egui::TextEdit::multiline(&mut curve_fit.pasted_points),
+                            )
+                        });
+                    ui.checkbox(&mut curve_fit.points_are_velocity, "Points are velocity");
+                }
+            }
+
+            if ui.button("Fit All Modes").clicked() {
+                let points_string = match curve_fit.source {
+                    FitSource::LookupTable => &rawaccel_convert_gui.settings.lookup_table_string,
+                    FitSource::Pasted => &curve_fit.pasted_points,
+                };
+                match rawaccel_convert::args_parser::parse_lookup_table(points_string) {
+                    Some(points) if !points.is_empty() => {
+                        curve_fit.targets = points
+                            .iter()
+                            .filter(|point| point.x > 0.0)
+                            .map(|point| rawaccel_convert::types::Point {
+                                x: point.x,
+                                y: match curve_fit.points_are_velocity {
+                                    true => point.y / point.x,
+                                    false => point.y,
+                                },
+                            })
+                            .collect();
+                        curve_fit.fits = vec![];
+                        curve_fit.shown_fits = vec![];
+                        curve_fit.pending_fits = fit::mode_fit_jobs();
+                        curve_fit.running_fits = vec![];
+                        curve_fit.fit_args = rawaccel_convert_gui.accel_args.clone();
+                        curve_fit.error = None;
+                    }
+                    _ => {
+                        curve_fit.fits = vec![];
+                        curve_fit.shown_fits = vec![];
+                        curve_fit.pending_fits = vec![];
+                        curve_fit.error = Some("Could not parse any points".to_string());
+                    }
+                }
+            }
+
+            //one mode per frame, large point sets would otherwise freeze the window for seconds
+            if !curve_fit.pending_fits.is_empty() {
+                let (mode, gain) = curve_fit.pending_fits.remove(0);
+                curve_fit.running_fits.push(fit::fit_default_parameters(
+                    &curve_fit.fit_args,
+                    &mode,
+                    gain,
+                    &curve_fit.targets,
+                ));
+                let total = curve_fit.running_fits.len() + curve_fit.pending_fits.len();
+                ui.add(
+                    egui::ProgressBar::new(curve_fit.running_fits.len() as f32 / total as f32)
+                        .text(format!("Fitting {}", fit::mode_label(&mode, gain))),
+                );
+                if curve_fit.pending_fits.is_empty() {
+                    curve_fit.fits = std::mem::take(&mut curve_fit.running_fits);
+                    fit::rank_fits(&mut curve_fit.fits);
+                    curve_fit.shown_fits = (0..curve_fit.fits.len()).map(|i| i < 3).collect();
+                } else {
+                    ctx.request_repaint();
+                }
+            }
+
+            if let Some(error) = &curve_fit.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+
+            let mut applied_fit = None;
+            if !curve_fit.fits.is_empty() {
+                ui.separator();
+                egui::Grid::new("curve_fit_ranking_grid").show(ui, |ui| {
+                    ui.label("Show");
+                    ui.label("Mode");
+                    ui.label("RMS Error");
+                    ui.label("Max Error");
+                    ui.end_row();
+
+                    for (i, mode_fit) in curve_fit.fits.iter().enumerate() {
+                        ui.checkbox(&mut curve_fit.shown_fits[i], "");
+                        ui.label(&mode_fit.label);
+                        ui.label(fit::format_parameter(mode_fit.result.rms_error));
+                        ui.label(fit::format_parameter(mode_fit.result.max_error));
+                        if ui.button("Apply").clicked() {
+                            applied_fit = Some(i);
+                        }
+                        ui.end_row();
+                    }
+                });
+            }
+
+            if let Some(i) = applied_fit {
+                let mode_fit = &curve_fit.fits[i];
+                fit::apply_fit(
+                    &mut rawaccel_convert_gui.settings,
+                    &mode_fit.parameters,
+                    &mode_fit.result.args,
+                );
+                rawaccel_convert_gui.accel_args.mode = mode_fit.result.args.mode.clone();
+                rawaccel_convert_gui.accel_args.gain = mode_fit.result.args.gain;
+            }
+        });
+    rawaccel_convert_gui.curve_fit.open = open;
+}
+
+fn curve_fit_lines(
+    curve_fit: &CurveFitState,
+    plot_samples_cache: &mut Vec<CachedPlotSamples>,
+    plot_accel_args: &AccelArgs,
+    plot_bounds: ([f64; 2], [f64; 2]),
+    transform: &PlotTransform,
+) -> Vec<plot_image::PlotLine> {
+    if !curve_fit.open || curve_fit.fits.is_empty() {
+        return vec![];
+    }
+
+    let mut lines = vec![];
+    for (i, mode_fit) in curve_fit.fits.iter().enumerate() {
+        if !curve_fit.shown_fits[i] {
+            continue;
+        }
+        let mut fit_args = mode_fit.result.args.clone();
+        fit_args.point_scaling = plot_accel_args.point_scaling.clone();
+        lines.push(plot_image::PlotLine {
+            name: mode_fit.label.clone(),
+            color: fit_color(i),
+            style: plot_image::LineStyle::Solid,
+            points: cached_plot_samples(
+                plot_samples_cache,
+                &fit_args,
+                plot_bounds.0[0],
+                plot_bounds.1[0],
+                transform,
+            ),
+        });
+    }
+
+    let target_points = curve_fit
+        .targets
+        .iter()
+        .map(|point| match plot_accel_args.point_scaling {
+            PointScaling::Sens => transform.point(point.x, point.y),
+            _ => transform.point(point.x, point.x * point.y),
+        })
+        .collect::<Vec<[f64; 2]>>();
+    lines.push(plot_image::PlotLine {
+        name: "Fit Targets".to_string(),
+        color: FIT_TARGET_COLOR,
+        style: plot_image::LineStyle::Markers,
+        points: target_points,
+    });
+    return lines;
+}
+
+fn fit_color(i: usize) -> egui::Color32 {
+    return FIT_CURVE_COLORS[i % FIT_CURVE_COLORS.len()];
+}
+
+fn add_convert_mode_window(rawaccel_convert_gui: &mut RawaccelConvertGui, ctx: &egui::Context) {
+    let mut open = rawaccel_convert_gui.convert_mode.open;
+    egui::Window::new("Convert to Mode")
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            let previous_mode = rawaccel_convert_gui.convert_mode.mode.clone();
+            let previous_gain = rawaccel_convert_gui.convert_mode.gain;
+
+            egui::Grid::new("convert_mode_grid").show(ui, |ui| {
+                ui.label("Target Mode");
+                ui.push_id("convert_mode_dropdown", |ui| {
+                    egui::ComboBox::from_label("")
+                        .selected_text(fit::mode_label(
+                            &rawaccel_convert_gui.convert_mode.mode,
+                            false,
+                        ))
+                        .show_ui(ui, |ui| {
+                            for mode in fit::parametric_modes() {
+                                let label = fit::mode_label(&mode, false);
+                                ui.selectable_value(
+                                    &mut rawaccel_convert_gui.convert_mode.mode,
+                                    mode,
+                                    label,
+                                );
+                            }
+                        });
+                });
+                ui.end_row();
+
+                ui.label("Gain");
+                ui.checkbox(&mut rawaccel_convert_gui.convert_mode.gain, "");
+                ui.end_row();
+            });
+
+            if previous_mode != rawaccel_convert_gui.convert_mode.mode
+                || previous_gain != rawaccel_convert_gui.convert_mode.gain
+            {
+                rawaccel_convert_gui.convert_mode.candidate = None;
+            }
+
+            if ui.button("Find Parameters").clicked() {
+                let current_args = sens_args(&rawaccel_convert_gui.accel_args);
+                let bounds = get_bounds(rawaccel_convert_gui, &current_args)
+                    .unwrap_or_else(|_| fallback_bounds(&current_args));
+                let targets = sample_curve(&current_args, bounds.0[0], bounds.1[0], 64)
+                    .into_iter()
+                    .map(|[x, y]| rawaccel_convert::types::Point { x, y })
+                    .collect::<Vec<_>>();
+
+                let convert_mode = &mut rawaccel_convert_gui.convert_mode;
+                let mut target_args = current_args.clone();
+                target_args.mode = convert_mode.mode.clone();
+                target_args.gain = convert_mode.gain;
+                let mut parameters = parameters::mode_parameters(&target_args);
+                parameters.retain(|parameter| parameter.free_by_default);
+
+                convert_mode.candidate = Some(fit::fit_mode(
+                    &current_args,
+                    &convert_mode.mode,
+                    convert_mode.gain,
+                    parameters,
+                    &targets,
+                ));
+            }
+
+            let mut commit = false;
+            if let Some(candidate) = &rawaccel_convert_gui.convert_mode.candidate {
+                ui.separator();
+                egui::Grid::new("convert_mode_result_grid").show(ui, |ui| {
+                    ui.label("Max Deviation");
+                    ui.label(fit::format_parameter(candidate.result.max_error));
+                    ui.end_row();
+
+                    ui.label("RMS Error");
+                    ui.label(fit::format_parameter(candidate.result.rms_error));
+                    ui.end_row();
+
+                    for parameter in &candidate.parameters {
+                        ui.label(parameter.label);
+                        ui.label(fit::format_parameter((parameter.get)(
+                            &candidate.result.args,
+                        )));
+                        ui.end_row();
+                    }
+                });
+                commit = ui.button("Commit").clicked();
+            }
+
+            if commit {
+                if let Some(candidate) = rawaccel_convert_gui.convert_mode.candidate.take() {
+                    fit::apply_fit(
+                        &mut rawaccel_convert_gui.settings,
+                        &candidate.parameters,
+                        &candidate.result.args,
+                    );
+                    rawaccel_convert_gui.accel_args.mode = candidate.result.args.mode;
+                    rawaccel_convert_gui.accel_args.gain = candidate.result.args.gain;
+                }
+            }
+        });
+    rawaccel_convert_gui.convert_mode.open = open;
+}
+
+fn convert_mode_lines(
+    convert_mode: &ConvertModeState,
+    plot_samples_cache: &mut Vec<CachedPlotSamples>,
+    plot_accel_args: &AccelArgs,
+    plot_bounds: ([f64; 2], [f64; 2]),
+    transform: &PlotTransform,
+) -> Vec<plot_image::PlotLine> {
+    if !convert_mode.open {
+        return vec![];
+    }
+    let Some(candidate) = &convert_mode.candidate else {
+        return vec![];
+    };
+    let mut candidate_args = candidate.result.args.clone();
+    candidate_args.point_scaling = plot_accel_args.point_scaling.clone();
+    return vec![
+        plot_image::PlotLine {
+            name: "Before".to_string(),
+            color: CURRENT_CURVE_COLOR,
+            style: plot_image::LineStyle::Solid,
+            points: cached_plot_samples(
+                plot_samples_cache,
+                plot_accel_args,
+                plot_bounds.0[0],
+                plot_bounds.1[0],
+                transform,
+            ),
+        },
+        plot_image::PlotLine {
+            name: format!("After: {}", candidate.label),
+            color: COMPARISON_CURVE_COLOR,
+            style: plot_image::LineStyle::Dashed,
+            points: cached_plot_samples(
+                plot_samples_cache,
+                &candidate_args,
+                plot_bounds.0[0],
+                plot_bounds.1[0],
+                transform,
+            ),
+        },
+    ];
+}
+
+fn add_dpi_wizard_window(rawaccel_convert_gui: &mut RawaccelConvertGui, ctx: &egui::Context) {
+    let mut open = rawaccel_convert_gui.dpi_wizard.open;
+    egui::Window::new("Change DPI")
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            let dpi_wizard = &mut rawaccel_convert_gui.dpi_wizard;
+            let mut parsed_dpis = vec![];
+            egui::Grid::new("dpi_wizard_grid").show(ui, |ui| {
+                for (label, dpi_string) in [
+                    ("Old DPI", &mut dpi_wizard.old_dpi_string),
+                    ("New DPI", &mut dpi_wizard.new_dpi_string),
+                ] {
+                    let mut color = ui.visuals().text_color();
+                    match expression::evaluate_whole(dpi_string) {
+                        Ok(ok) if ok > 0 => parsed_dpis.push(ok),
+                        _ => {
+                            color = ui.visuals().error_fg_color;
+                        }
+                    }
+                    ui.add(egui::Label::new(egui::RichText::new(label).color(color)).selectable(false));
+                    ui.text_edit_singleline(dpi_string);
+                    ui.end_row();
+                }
+            });
+
+            let (old_dpi, new_dpi) = match parsed_dpis[..] {
+                [old_dpi, new_dpi] => (old_dpi, new_dpi),
+                _ => return,
+            };
+
+            let mut old_args = rawaccel_convert_gui.accel_args.clone();
+            old_args.dpi = old_dpi;
+            old_args.point_scaling = PointScaling::Velocity;
+            let new_args = dpi_change::rescale_args(&old_args, old_dpi, new_dpi);
+            let ratio = new_dpi as f64 / old_dpi as f64;
+
+            let max_speed = (old_dpi / 20) as f64;
+            let mut old_points = vec![];
+            let mut new_points = vec![];
+            let mut max_deviation: f64 = 0.0;
+            for [x, old_y] in sample_curve(&old_args, 0.1, max_speed, 256) {
+                let new_y = get_point(x * ratio, &new_args);
+                let physical_speed = units::InputUnit::CmPerS.speed_from_counts_per_ms(x, old_dpi);
+                old_points.push([physical_speed, old_y]);
+                new_points.push([physical_speed, new_y]);
+                if old_y != 0.0 {
+                    max_deviation = max_deviation.max(((new_y - old_y) / old_y).abs());
+                }
+            }
+
+            ui.label(format!(
+                "Max deviation over the plotted range: {}%",
+                fit::format_parameter(max_deviation * 100.0)
+            ));
+
+            egui_plot::Plot::new("dpi_wizard_plot")
+                .height(200.0)
+                .width(400.0)
+                .legend(egui_plot::Legend::default())
+                .x_axis_label("Hand Speed (cm/s)")
+                .y_axis_label("Output Velocity")
+                .allow_zoom(false)
+                .allow_drag(false)
+                .allow_scroll(false)
+                .show(ui, |plot_ui| {
+                    plot_ui.line(
+                        egui_plot::Line::new(egui_plot::PlotPoints::new(old_points))
+                            .color(egui::Color32::from_rgb(100, 100, 200))
+                            .name(format!("{} DPI", old_dpi)),
+                    );
+                    plot_ui.line(
+                        egui_plot::Line::new(egui_plot::PlotPoints::new(new_points))
+                            .color(egui::Color32::from_rgb(200, 120, 60))
+                            .style(egui_plot::LineStyle::dashed_loose())
+                            .name(format!("{} DPI", new_dpi)),
+                    );
+                });
+
+            if rawaccel_convert_gui.accel_args.mode != AccelMode::Lookup
+                && !rawaccel_convert_gui.settings.lookup_table_string.trim().is_empty()
+            {
+                ui.label("LUT Points are only rescaled with Look Up Table as the curve type, its Apply As setting decides how.");
+            }
+
+            if ui.button("Apply").clicked() {
+                let lookup_velocity = (rawaccel_convert_gui.accel_args.mode == AccelMode::Lookup)
+                    .then_some(rawaccel_convert_gui.accel_args.gain);
+                rawaccel_convert_gui.settings = dpi_change::rescale_settings(
+                    &rawaccel_convert_gui.settings,
+                    lookup_velocity,
+                    old_dpi,
+                    new_dpi,
+                );
+                rawaccel_convert_gui.dpi_wizard.old_dpi_string = new_dpi.to_string();
+            }
+        });
+    rawaccel_convert_gui.dpi_wizard.open = open;
+}
+
+fn add_game_window(rawaccel_convert_gui: &mut RawaccelConvertGui, ctx: &egui::Context) {
+    let mut open = rawaccel_convert_gui.game_panel.open;
+    egui::Window::new("Game Sensitivity")
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            egui::Grid::new("game_window_grid").show(ui, |ui| {
+                add_game_preset(rawaccel_convert_gui, ui);
+                ui.end_row();
+
+                add_game_yaw(rawaccel_convert_gui, ui);
+                ui.end_row();
+
+                add_game_sens(rawaccel_convert_gui, ui);
+                ui.end_row();
+
+                //a hand edited yaw no longer belongs to the selected game
+                let settings = &mut rawaccel_convert_gui.settings;
+                let game_yaw = games::game_yaw(&settings.game_name, &settings.custom_games);
+                let yaw_matches = game_yaw.is_some_and(|yaw| {
+                    (yaw - rawaccel_convert_gui.game_yaw).abs() <= yaw.abs() * 1e-5
+                });
+                if !yaw_matches && expression::evaluate(&settings.game_yaw_string).is_ok() {
+                    settings.game_name = games::CUSTOM_YAW_NAME.to_string();
+                }
+
+                ui.add(
+                    egui::TextEdit::singleline(
+                        &mut rawaccel_convert_gui.game_panel.custom_game_name,
+                    )
+                    .hint_text("Custom game name"),
+                );
+                let custom_game_name = rawaccel_convert_gui.game_panel.custom_game_name.trim();
+                if ui
+                    .add_enabled(
+                        !custom_game_name.is_empty(),
+                        egui::Button::new("Save Custom"),
+                    )
+                    .clicked()
+                {
+                    let custom_game = games::CustomGame {
+                        name: custom_game_name.to_string(),
+                        yaw: rawaccel_convert_gui.game_yaw,
+                    };
+                    let settings = &mut rawaccel_convert_gui.settings;
+                    settings
+                        .custom_games
+                        .retain(|game| game.name != custom_game.name);
+                    settings.game_name = custom_game.name.clone();
+                    settings.custom_games.push(custom_game);
+                }
+                ui.end_row();
+            });
+
+            let selected_custom_game = rawaccel_convert_gui
+                .settings
+                .custom_games
+                .iter()
+                .position(|game| game.name == rawaccel_convert_gui.settings.game_name);
+            if let Some(i) = selected_custom_game {
+                if ui.button("Remove Custom Game").clicked() {
+                    rawaccel_convert_gui.settings.custom_games.remove(i);
+                }
+            }
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.selectable_value(
+                    &mut rawaccel_convert_gui.game_panel.degrees_per_cm,
+                    false,
+                    "cm/360",
+                );
+                ui.selectable_value(
+                    &mut rawaccel_convert_gui.game_panel.degrees_per_cm,
+                    true,
+                    "°/cm",
+                );
+            });
+
+            let args = sens_args(&rawaccel_convert_gui.accel_args);
+            let degrees_per_cm = rawaccel_convert_gui.game_panel.degrees_per_cm;
+            let yaw = rawaccel_convert_gui.game_yaw;
+            let game_sens = rawaccel_convert_gui.game_sens;
+            let game_value = |speed: f64| {
+                let cm_per_360 =
+                    units::cm_per_360(get_point(speed, &args), args.dpi, yaw, game_sens);
+                match degrees_per_cm {
+                    true => 360.0 / cm_per_360,
+                    false => cm_per_360,
+                }
+            };
+            let unit_label = match degrees_per_cm {
+                true => "°/cm",
+                false => "cm/360",
+            };
+
+            let max_speed = (args.dpi / 20) as f64;
+            let points = sample_curve(&args, 0.1, max_speed, 256)
+                .into_iter()
+                .map(|[x, _]| {
+                    [
+                        units::InputUnit::CmPerS.speed_from_counts_per_ms(x, args.dpi),
+                        game_value(x),
+                    ]
+                })
+                .collect::<Vec<[f64; 2]>>();
+
+            egui_plot::Plot::new("game_plot")
+                .height(200.0)
+                .width(400.0)
+                .x_axis_label("Hand Speed (cm/s)")
+                .y_axis_label(unit_label)
+                .allow_zoom(false)
+                .allow_drag(false)
+                .allow_scroll(false)
+                .show(ui, |plot_ui| {
+                    plot_ui.line(
+                        egui_plot::Line::new(egui_plot::PlotPoints::new(points))
+                            .color(egui::Color32::from_rgb(100, 100, 200)),
+                    );
+                });
+
+            egui::Grid::new("game_speed_table_grid").show(ui, |ui| {
+                ui.label("Hand Speed (cm/s)");
+                ui.label(unit_label);
+                ui.end_row();
+
+                for hand_speed in [5.0, 10.0, 25.0, 50.0, 100.0, 200.0] {
+                    let speed =
+                        units::InputUnit::CmPerS.speed_to_counts_per_ms(hand_speed, args.dpi);
+                    ui.label(format!("{}", hand_speed));
+                    ui.label(format!("{:.2}", game_value(speed)));
+                    ui.end_row();
+                }
+            });
+        });
+    rawaccel_convert_gui.game_panel.open = open;
+}
+
+fn add_recorder_window(rawaccel_convert_gui: &mut RawaccelConvertGui, ctx: &egui::Context) {
+    let mut open = rawaccel_convert_gui.recorder.open;
+    egui::Window::new("Motion Recorder")
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                let record_text = match rawaccel_convert_gui.recorder.recording {
+                    true => "Stop",
+                    false => "Record",
+                };
+                if ui.button(record_text).clicked() {
+                    rawaccel_convert_gui.recorder.recording = !rawaccel_convert_gui.recorder.recording;
+                }
+                if ui.button("Clear").clicked() {
+                    rawaccel_convert_gui.motion_recording = MotionRecording::default();
+                    rawaccel_convert_gui.recorder.used_pointer_fallback = false;
+                }
+                ui.checkbox(
+                    &mut rawaccel_convert_gui.recorder.show_histogram,
+                    "Show on plot",
+                );
+            });
+
+            let (rect, _) =
+                ui.allocate_exact_size(egui::vec2(360.0, 200.0), egui::Sense::hover());
+            ui.painter()
+                .rect_filled(rect, 4.0, ui.visuals().extreme_bg_color);
+            ui.painter().text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                match rawaccel_convert_gui.recorder.recording {
+                    true => "Recording, move the mouse here",
+                    false => "Press Record, then move the mouse here",
+                },
+                egui::FontId::default(),
+                ui.visuals().weak_text_color(),
+            );
+
+            if rawaccel_convert_gui.recorder.recording {
+                record_motion(rawaccel_convert_gui, ui, rect);
+            }
+
+            let recording = &rawaccel_convert_gui.motion_recording;
+            ui.label(format!(
+                "{} events over {:.1} s",
+                recording.events.len(),
+                recording.duration_ms() / 1000.0
+            ));
+            if rawaccel_convert_gui.recorder.used_pointer_fallback {
+                ui.colored_label(
+                    ui.visuals().warn_fg_color,
+                    "Raw mouse input is unavailable, cursor movement in pixels was recorded instead",
+                );
+            }
+            add_motion_stats(rawaccel_convert_gui, ui);
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut rawaccel_convert_gui.recorder.file_path)
+                        .desired_width(180.0),
+                );
+                if ui.button("Save").clicked() {
+                    let csv = rawaccel_convert_gui.motion_recording.to_csv();
+                    rawaccel_convert_gui.recorder.status = Some(
+                        file_io::save_file(&rawaccel_convert_gui.recorder.file_path, csv.as_bytes())
+                            .map(|_| "Saved".to_string()),
+                    );
+                }
+                if ui.button("Load").clicked() {
+                    let loaded = file_io::load_file(&rawaccel_convert_gui.recorder.file_path);
+                    load_motion_recording(rawaccel_convert_gui, loaded);
+                }
+            });
+            ui.label("Recordings can also be loaded by dropping the file onto the window. Output of `libinput record` and `libinput debug-events` is read as well.");
+
+            let dropped_files = ctx.input(|i| i.raw.dropped_files.clone());
+            if let Some(file) = dropped_files.first() {
+                rawaccel_convert_gui.recorder.file_path = file.name.clone();
+                load_motion_recording(rawaccel_convert_gui, file_io::read_dropped_file(file));
+            }
+
+            match &rawaccel_convert_gui.recorder.status {
+                Some(Ok(ok)) => {
+                    ui.label(ok);
+                }
+                Some(Err(err)) => {
+                    ui.colored_label(ui.visuals().error_fg_color, err);
+                }
+                None => {}
+            }
+        });
+    rawaccel_convert_gui.recorder.open = open;
+}
+
+fn add_motion_stats(rawaccel_convert_gui: &mut RawaccelConvertGui, ui: &mut egui::Ui) {
+    let cap_speed = cap_speed(&rawaccel_convert_gui.accel_args);
+    let stats = match motion::motion_stats(
+        &rawaccel_convert_gui.motion_recording.speed_samples(),
+        cap_speed,
+    ) {
+        Some(some) => some,
+        None => return,
+    };
+    let dpi = rawaccel_convert_gui.accel_args.dpi;
+    let format_speed = |speed: f64| -> String {
+        return format!(
+            "{:.2} counts/ms ({:.1} cm/s)",
+            speed,
+            units::InputUnit::CmPerS.speed_from_counts_per_ms(speed, dpi)
+        );
+    };
+
+    egui::Grid::new("motion_stats").show(ui, |ui| {
+        ui.label("Median Speed:");
+        ui.label(format_speed(stats.median_speed));
+        ui.end_row();
+        ui.label("95th Percentile Speed:");
+        ui.label(format_speed(stats.p95_speed));
+        ui.end_row();
+        ui.label("Time Above Cap:");
+        match (stats.time_above_cap, cap_speed) {
+            (Some(time_above_cap), Some(cap_speed)) => ui.label(format!(
+                "{:.1}% (cap at {})",
+                time_above_cap * 100.0,
+                format_speed(cap_speed)
+            )),
+            _ => ui.label("Current curve has no cap"),
+        };
+        ui.end_row();
+    });
+}
+
+fn add_replay_window(rawaccel_convert_gui: &mut RawaccelConvertGui, ctx: &egui::Context) {
+    let mut open = rawaccel_convert_gui.replay.open;
+    egui::Window::new("Motion Replay")
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                if ui.button("Compare With Current Curve").clicked() {
+                    rawaccel_convert_gui.replay.comparison =
+                        Some(rawaccel_convert_gui.accel_args.clone());
+                }
+                if let Some(comparison) = &rawaccel_convert_gui.replay.comparison {
+                    ui.label(format!(
+                        "Comparing with {}",
+                        fit::mode_label(&comparison.mode, comparison.gain)
+                    ));
+                    if ui.button("Clear").clicked() {
+                        rawaccel_convert_gui.replay.comparison = None;
+                    }
+                }
+            });
+
+            let duration_seconds = rawaccel_convert_gui.motion_recording.duration_ms() / 1000.0;
+            if duration_seconds <= 0.0 {
+                ui.label("Record or load motion in the Motion Recorder to replay it.");
+                return;
+            }
+
+            let replay_state = &mut rawaccel_convert_gui.replay;
+            egui::Grid::new("replay_range").show(ui, |ui| {
+                ui.label("Start (s):");
+                ui.add(egui::Slider::new(
+                    &mut replay_state.start_seconds,
+                    0.0..=duration_seconds,
+                ));
+                ui.end_row();
+                ui.label("Length (s):");
+                ui.add(
+                    egui::Slider::new(&mut replay_state.length_seconds, 0.05..=duration_seconds)
+                        .logarithmic(true),
+                );
+                ui.end_row();
+            });
+
+            let slice = rawaccel_convert_gui.motion_recording.slice(
+                replay_state.start_seconds * 1000.0,
+                (replay_state.start_seconds + replay_state.length_seconds) * 1000.0,
+            );
+            let current_args = sens_args(&rawaccel_convert_gui.accel_args);
+            let current = slice.replay(|speed| get_point(speed, &current_args));
+            let comparison = replay_state.comparison.as_ref().map(|comparison| {
+                let comparison_args = sens_args(comparison);
+                slice.replay(|speed| get_point(speed, &comparison_args))
+            });
+
+            //screen y grows downwards
+            let flip = |path: &Vec<[f64; 2]>| -> Vec<[f64; 2]> {
+                return path.iter().map(|point| [point[0], -point[1]]).collect();
+            };
+            let current_color = egui::Color32::from_rgb(100, 100, 200);
+            let comparison_color = egui::Color32::from_rgb(200, 120, 60);
+
+            ui.horizontal(|ui| {
+                egui_plot::Plot::new("replay_raw_path")
+                    .width(300.0)
+                    .height(300.0)
+                    .data_aspect(1.0)
+                    .link_axis("replay_paths", [true, true])
+                    .show(ui, |plot_ui| {
+                        plot_ui.line(
+                            egui_plot::Line::new(egui_plot::PlotPoints::new(flip(&current.raw)))
+                                .color(egui::Color32::from_gray(128))
+                                .name("Raw"),
+                        );
+                    });
+                egui_plot::Plot::new("replay_output_path")
+                    .width(300.0)
+                    .height(300.0)
+                    .data_aspect(1.0)
+                    .legend(egui_plot::Legend::default())
+                    .link_axis("replay_paths", [true, true])
+                    .show(ui, |plot_ui| {
+                        plot_ui.line(
+                            egui_plot::Line::new(egui_plot::PlotPoints::new(flip(&current.output)))
+                                .color(current_color)
+                                .name("Current"),
+                        );
+                        if let Some(comparison) = &comparison {
+                            plot_ui.line(
+                                egui_plot::Line::new(egui_plot::PlotPoints::new(flip(
+                                    &comparison.output,
+                                )))
+                                .color(comparison_color)
+                                .style(egui_plot::LineStyle::dashed_loose())
+                                .name("Comparison"),
+                            );
+                        }
+                    });
+            });
+
+            ui.label("Sens per event (output counts / input counts)");
+            egui_plot::Plot::new("replay_sens")
+                .width(606.0)
+                .height(150.0)
+                .x_axis_label("Time (ms)")
+                .show(ui, |plot_ui| {
+                    plot_ui.line(
+                        egui_plot::Line::new(egui_plot::PlotPoints::new(current.sens))
+                            .color(current_color)
+                            .name("Current"),
+                    );
+                    if let Some(comparison) = comparison {
+                        plot_ui.line(
+                            egui_plot::Line::new(egui_plot::PlotPoints::new(comparison.sens))
+                                .color(comparison_color)
+                                .style(egui_plot::LineStyle::dashed_loose())
+                                .name("Comparison"),
+                        );
+                    }
+                });
+        });
+    rawaccel_convert_gui.replay.open = open;
+}
+
+fn add_metrics_window(rawaccel_convert_gui: &mut RawaccelConvertGui, ctx: &egui::Context) {
+    let mut open = rawaccel_convert_gui.metrics.open;
+    egui::Window::new("Usage Metrics")
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            let samples = rawaccel_convert_gui.motion_recording.speed_samples();
+            if samples.is_empty() {
+                ui.label("Record or load motion in the Motion Recorder to weigh curves by it.");
+                return;
+            }
+
+            //every curve currently on screen
+            let mut curves = vec![(
+                format!(
+                    "Current: {}",
+                    fit::mode_label(
+                        &rawaccel_convert_gui.accel_args.mode,
+                        rawaccel_convert_gui.accel_args.gain
+                    )
+                ),
+                rawaccel_convert_gui.accel_args.clone(),
+            )];
+            if let Some(comparison) = &rawaccel_convert_gui.replay.comparison {
+                curves.push((
+                    format!(
+                        "Comparison: {}",
+                        fit::mode_label(&comparison.mode, comparison.gain)
+                    ),
+                    comparison.clone(),
+                ));
+            }
+            if rawaccel_convert_gui.convert_mode.open {
+                if let Some(candidate) = &rawaccel_convert_gui.convert_mode.candidate {
+                    curves.push((
+                        format!("Converted: {}", candidate.label),
+                        candidate.result.args.clone(),
+                    ));
+                }
+            }
+            if rawaccel_convert_gui.curve_fit.open {
+                for (fit, shown) in rawaccel_convert_gui
+                    .curve_fit
+                    .fits
+                    .iter()
+                    .zip(&rawaccel_convert_gui.curve_fit.shown_fits)
+                {
+                    if *shown {
+                        curves.push((format!("Fit: {}", fit.label), fit.result.args.clone()));
+                    }
+                }
+            }
+
+            let mut rows = curves
+                .into_iter()
+                .filter_map(|(label, args)| {
+                    metrics::usage_metrics(
+                        &samples,
+                        &args,
+                        rawaccel_convert_gui.game_yaw,
+                        rawaccel_convert_gui.game_sens,
+                    )
+                    .map(|usage_metrics| (label, usage_metrics))
+                })
+                .collect::<Vec<(String, metrics::UsageMetrics)>>();
+
+            let sort_by = rawaccel_convert_gui.metrics.sort_by;
+            let sort_key = |usage_metrics: &metrics::UsageMetrics| -> f64 {
+                match sort_by {
+                    MetricColumn::MeanSens => usage_metrics.mean_sens,
+                    MetricColumn::Cm360Spread => {
+                        usage_metrics.cm_per_360_high - usage_metrics.cm_per_360_low
+                    }
+                    MetricColumn::Capped => usage_metrics.capped_fraction.unwrap_or_default(),
+                    MetricColumn::DecreasingVelocity => usage_metrics.decreasing_velocity_fraction,
+                }
+            };
+            rows.sort_by(|a, b| sort_key(&a.1).total_cmp(&sort_key(&b.1)));
+
+            ui.label("Sorted from lowest to highest, click a column to sort by it.");
+            egui::Grid::new("usage_metrics")
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("Curve");
+                    for (column, label) in [
+                        (MetricColumn::MeanSens, "Mean Sens"),
+                        (MetricColumn::Cm360Spread, "cm/360 (5%-95%)"),
+                        (MetricColumn::Capped, "Capped"),
+                        (MetricColumn::DecreasingVelocity, "Velocity Decreasing"),
+                    ] {
+                        ui.selectable_value(
+                            &mut rawaccel_convert_gui.metrics.sort_by,
+                            column,
+                            label,
+                        );
+                    }
+                    ui.end_row();
+
+                    for (label, usage_metrics) in &rows {
+                        ui.label(label);
+                        ui.label(format!("{:.3}", usage_metrics.mean_sens));
+                        ui.label(format!(
+                            "{:.1}-{:.1}",
+                            usage_metrics.cm_per_360_low, usage_metrics.cm_per_360_high
+                        ));
+                        ui.label(match usage_metrics.capped_fraction {
+                            Some(some) => format!("{:.1}%", some * 100.0),
+                            None => "No cap".to_string(),
+                        });
+                        ui.label(format!(
+                            "{:.1}%",
+                            usage_metrics.decreasing_velocity_fraction * 100.0
+                        ));
+                        ui.end_row();
+                    }
+                });
+        });
+    rawaccel_convert_gui.metrics.open = open;
+}
+
+//seconds for the scrubber to go from the first value to the last
+const SWEEP_PLAY_SECONDS: f64 = 3.0;
+const MAX_SWEEP_COUNT: usize = 32;
+
+fn add_sweep_window(rawaccel_convert_gui: &mut RawaccelConvertGui, ctx: &egui::Context) {
+    let mut open = rawaccel_convert_gui.sweep.open;
+    egui::Window::new("Parameter Sweep")
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            let shown_parameters = [
+                vec![&parameters::SENS_MULTIPLIER],
+                parameters::mode_parameters(&rawaccel_convert_gui.accel_args),
+            ]
+            .concat();
+            let sweep = &mut rawaccel_convert_gui.sweep;
+            //the mode changed or nothing is picked yet
+            if !shown_parameters
+                .iter()
+                .any(|parameter| parameter.id == sweep.parameter_id)
+            {
+                select_sweep_parameter(
+                    sweep,
+                    shown_parameters[0],
+                    &rawaccel_convert_gui.accel_args,
+                );
+            }
+            let parameter = *shown_parameters
+                .iter()
+                .find(|parameter| parameter.id == sweep.parameter_id)
+                .unwrap_or(&shown_parameters[0]);
+
+            let mut values = None;
+            egui::Grid::new("sweep_grid").show(ui, |ui| {
+                ui.add(egui::Label::new("Parameter").selectable(false));
+                let mut selected = parameter;
+                ui.push_id("sweep_parameter_dropdown", |ui| {
+                    egui::ComboBox::from_label("")
+                        .selected_text(parameter.label)
+                        .show_ui(ui, |ui| {
+                            for shown_parameter in &shown_parameters {
+                                if ui
+                                    .selectable_label(
+                                        shown_parameter.id == parameter.id,
+                                        shown_parameter.label,
+                                    )
+                                    .clicked()
+                                {
+                                    selected = shown_parameter;
+                                }
+                            }
+                        });
+                });
+                if selected.id != parameter.id {
+                    select_sweep_parameter(sweep, selected, &rawaccel_convert_gui.accel_args);
+                }
+                ui.end_row();
+
+                let mut parsed = vec![];
+                for (label, string) in [
+                    ("From", &mut sweep.from_string),
+                    ("To", &mut sweep.to_string),
+                    ("Count", &mut sweep.count_string),
+                ] {
+                    let value = expression::evaluate(string);
+                    let mut color = ui.visuals().text_color();
+                    if value.is_err() {
+                        color = ui.visuals().error_fg_color;
+                    }
+                    ui.add(
+                        egui::Label::new(egui::RichText::new(label).color(color)).selectable(false),
+                    );
+                    ui.add(egui::TextEdit::singleline(string).desired_width(80.0));
+                    ui.end_row();
+                    parsed.push(value);
+                }
+                if let [Ok(from), Ok(to), Ok(count)] = parsed[..] {
+                    let count = (count.round() as usize).clamp(2, MAX_SWEEP_COUNT);
+                    values = Some((from, to, sweep::sweep_values(parameter, from, to, count)));
+                }
+            });
+
+            let (from, to, values) = match values {
+                Some(some) => some,
+                None => {
+                    sweep.curves = vec![];
+                    sweep.generated_for = None;
+                    return;
+                }
+            };
+
+            //regenerating every frame would stall the ui, and the scrubber changes the swept value itself
+            let mut sweep_args = rawaccel_convert_gui.accel_args.clone();
+            (parameter.set)(&mut sweep_args, 0.0);
+            let generated_for = Some((sweep_args.clone(), parameter.id, values.clone()));
+            if sweep.generated_for != generated_for {
+                sweep.curves = sweep::sweep(&sweep_args, parameter, &values);
+                sweep.generated_for = generated_for;
+            }
+            if sweep.curves.len() < values.len() {
+                ui.colored_label(
+                    ui.visuals().warn_fg_color,
+                    format!(
+                        "⚠ {} of {} values don't make a valid curve",
+                        values.len() - sweep.curves.len(),
+                        values.len()
+                    ),
+                );
+            }
+
+            ui.separator();
+            let mut scrubbed = false;
+            ui.horizontal(|ui| {
+                let play_label = match sweep.playing {
+                    true => "⏸",
+                    false => "▶",
+                };
+                if ui.button(play_label).clicked() {
+                    sweep.playing = !sweep.playing;
+                }
+                scrubbed |= ui
+                    .add(egui::Slider::new(&mut sweep.scrub_position, 0.0..=1.0).show_value(false))
+                    .changed();
+                ui.label(fit::format_parameter(sweep::interpolate(
+                    parameter,
+                    from,
+                    to,
+                    sweep.scrub_position,
+                )));
+            });
+            if sweep.playing {
+                let step = ui.input(|input| input.stable_dt) as f64 / SWEEP_PLAY_SECONDS;
+                sweep.scrub_position += match sweep.playing_backwards {
+                    true => -step,
+                    false => step,
+                };
+                if sweep.scrub_position >= 1.0 || sweep.scrub_position <= 0.0 {
+                    sweep.playing_backwards = sweep.scrub_position >= 1.0;
+                    sweep.scrub_position = sweep.scrub_position.clamp(0.0, 1.0);
+                }
+                scrubbed = true;
+                ui.ctx().request_repaint();
+            }
+            if scrubbed {
+                let value = sweep::interpolate(parameter, from, to, sweep.scrub_position);
+                apply_parameter_value(rawaccel_convert_gui, parameter, value);
+            }
+        });
+    rawaccel_convert_gui.sweep.open = open;
+    if !open {
+        rawaccel_convert_gui.sweep.playing = false;
+    }
+}
+
+fn select_sweep_parameter(sweep: &mut SweepState, parameter: &Parameter, args: &AccelArgs) {
+    let (from, to) = (parameter.slider_range)(args);
+    sweep.parameter_id = parameter.id;
+    sweep.from_string = fit::format_parameter(from);
+    sweep.to_string = fit::format_parameter(to);
+    if sweep.count_string.is_empty() {
+        sweep.count_string = "8".to_string();
+    }
+    sweep.scrub_position = 0.0;
+}
+
+fn sweep_lines(sweep: &SweepState, transform: &PlotTransform) -> Vec<plot_image::PlotLine> {
+    if !sweep.open {
+        return vec![];
+    }
+    let label = parameters::all_parameters()
+        .into_iter()
+        .find(|parameter| parameter.id == sweep.parameter_id)
+        .map(|parameter| parameter.label)
+        .unwrap_or_default();
+    return sweep
+        .curves
+        .iter()
+        .enumerate()
+        .map(|(i, curve)| {
+            let points = curve
+                .points
+                .iter()
+                .map(|point| [point.x, point.y])
+                .collect::<Vec<[f64; 2]>>();
+            plot_image::PlotLine {
+                name: format!("{} = {}", label, fit::format_parameter(curve.value)),
+                color: sweep_curve_color(sweep, i),
+                style: plot_image::LineStyle::Solid,
+                points: transform_points(points, transform),
+            }
+        })
+        .collect();
+}
+
+fn sweep_curve_color(sweep: &SweepState, i: usize) -> egui::Color32 {
+    let last = sweep.curves.len().max(2) - 1;
+    return sweep_color(i as f32 / last as f32);
+}
+
+//blue for the first value through to orange for the last
+fn sweep_color(fraction: f32) -> egui::Color32 {
+    let lerp =
+        |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * fraction).round() as u8;
+    return egui::Color32::from_rgb(lerp(60, 235), lerp(110, 130), lerp(230, 40));
+}
+
+fn comparison_lines(
+    replay: &ReplayState,
+    plot_samples_cache: &mut Vec<CachedPlotSamples>,
+    plot_accel_args: &AccelArgs,
+    plot_bounds: ([f64; 2], [f64; 2]),
+    transform: &PlotTransform,
+) -> Vec<plot_image::PlotLine> {
+    let Some(comparison) = &replay.comparison else {
+        return vec![];
+    };
+    let mut comparison_args = comparison.clone();
+    comparison_args.point_scaling = plot_accel_args.point_scaling.clone();
+    return vec![plot_image::PlotLine {
+        name: format!(
+            "Comparison: {}",
+            fit::mode_label(&comparison.mode, comparison.gain)
+        ),
+        color: COMPARISON_CURVE_COLOR,
+        style: plot_image::LineStyle::Dashed,
+        points: cached_plot_samples(
+            plot_samples_cache,
+            &comparison_args,
+            plot_bounds.0[0],
+            plot_bounds.1[0],
+            transform,
+        ),
+    }];
+}
+
+const STACKED_PLOT_SAMPLES: usize = 256;
+
+//what a stacked plot shows of the curve at each speed, in the pane's unit before the log axis
+type PaneValue = fn(&curve_analysis::Readout, &PlotTransform) -> f64;
+
+struct StackedPane {
+    label: String,
+    //the graph's transform with the pane's output unit
+    transform: PlotTransform,
+    value: PaneValue,
+}
+
+fn stacked_panes(stacked_plots: &StackedPlots, transform: &PlotTransform) -> Vec<StackedPane> {
+    //sens follows the graph's cm/360 choice, velocity is shown in the speed unit and gain has none
+    let sens_output = match transform.units.output {
+        OutputUnit::Cm360 => OutputUnit::Cm360,
+        OutputUnit::Sens | OutputUnit::Velocity => OutputUnit::Sens,
+    };
+    return [
+        (
+            stacked_plots.sens,
+            sens_output.label().to_string(),
+            sens_output,
+            (|readout, _| readout.sens) as PaneValue,
+        ),
+        (
+            stacked_plots.velocity,
+            format!(
+                "{} ({})",
+                OutputUnit::Velocity.label(),
+                transform.units.input.label()
+            ),
+            OutputUnit::Velocity,
+            |readout, transform| {
+                transform
+                    .units
+                    .input
+                    .speed_from_counts_per_ms(readout.velocity, transform.dpi)
+            },
+        ),
+        (
+            stacked_plots.gain,
+            "Gain".to_string(),
+            OutputUnit::Sens,
+            |readout, _| readout.gain,
+        ),
+    ]
+    .into_iter()
+    .filter(|(shown, _, _, _)| *shown)
+    .map(|(_, label, output, value)| StackedPane {
+        label,
+        transform: PlotTransform {
+            units: PlotUnits {
+                output,
+                ..transform.units
+            },
+            ..*transform
+        },
+        value,
+    })
+    .collect();
+}
+
+fn stacked_pane_lines(
+    curves: &[PlottedCurve],
+    pane: &StackedPane,
+    min_x: f64,
+    max_x: f64,
+) -> Vec<plot_image::PlotLine> {
+    let transform = pane.transform;
+    let min_x = transform.min_speed(min_x, max_x);
+    return curves
+        .iter()
+        .map(|curve| {
+            let points = (0..=STACKED_PLOT_SAMPLES)
+                .filter_map(|sample| {
+                    //even steps on the plotted axis
+                    let t = sample as f64 / STACKED_PLOT_SAMPLES as f64;
+                    let speed = match transform.units.log_x {
+                        true => min_x * (max_x / min_x).powf(t),
+                        false => min_x + (max_x - min_x) * t,
+                    };
+                    let readout = curve_analysis::readout(&curve.args, speed)?;
+                    Some(transform.point(speed, (pane.value)(&readout, &transform)))
+                })
+                //zero has no place on a log axis
+                .filter(|[x, y]| x.is_finite() && y.is_finite())
+                .collect::<Vec<[f64; 2]>>();
+            plot_image::PlotLine {
+                name: curve.name.clone(),
+                color: curve.color,
+                style: curve.style,
+                points,
+            }
+        })
+        .collect();
+}
+
+fn add_stacked_plots(
+    rawaccel_convert_gui: &mut RawaccelConvertGui,
+    ui: &mut egui::Ui,
+    max_x: f64,
+    transform: &PlotTransform,
+) {
+    let panes = stacked_panes(&rawaccel_convert_gui.stacked_plots, transform);
+    if panes.is_empty() {
+        ui.centered_and_justified(|ui| {
+            ui.label("Pick a view to show under Stacked Views");
+        });
+        return;
+    }
+
+    let curves = plotted_curves(rawaccel_convert_gui);
+    let transform = *transform;
+    let spacing = ui.spacing().item_spacing.y;
+    let pane_height =
+        (ui.available_height() - spacing * (panes.len() - 1) as f32) / panes.len() as f32;
+    for pane in panes {
+        let pane_transform = pane.transform;
+        let label = pane.label.clone();
+        let mut y_axis = egui_plot::AxisHints::new_y().label(label.clone());
+        if transform.units.log_y {
+            y_axis = y_axis
+                .formatter(move |mark, _range| format_log_mark(pane_transform.axis_y(mark.value)));
+        }
+        let mut plot = egui_plot::Plot::new(("stacked_plot", label.clone()))
+            .height(pane_height)
+            .legend(egui_plot::Legend::default())
+            .link_axis("stacked_plots", [true, false])
+            .link_cursor("stacked_plots", egui::Vec2b::new(true, false))
+            .allow_zoom([true, false])
+            .allow_drag([true, false])
+            .allow_scroll([true, false])
+            .include_x(transform.x(transform.min_speed(0.0, max_x)))
+            .include_x(transform.x(max_x))
+            .custom_x_axes(plot_x_axes(&transform).into_iter().take(1).collect())
+            .custom_y_axes(vec![y_axis])
+            .label_formatter(move |name, point| {
+                format!(
+                    "{}\n{:.2} {}\n{:.4} {}",
+                    name,
+                    transform.axis_x(point.x),
+                    transform.units.input.label(),
+                    pane_transform.axis_y(point.y),
+                    label
+                )
+            });
+        if transform.units.log_x {
+            plot = plot.x_grid_spacer(log_grid_spacer);
+        }
+        match transform.units.log_y {
+            true => plot = plot.y_grid_spacer(log_grid_spacer),
+            false => plot = plot.include_y(0.0),
+        }
+        plot.show(ui, |plot_ui| {
+            //sample what is visible so zooming in keeps the curves smooth
+            let bounds = plot_ui.plot_bounds();
+            let (min_x, max_x) = match bounds.width() > 0.0 && bounds.max()[0].is_finite() {
+                true => (
+                    transform.inverse_x(bounds.min()[0]).max(0.0),
+                    transform.inverse_x(bounds.max()[0]),
+                ),
+                false => (0.0, max_x),
+            };
+            for line in stacked_pane_lines(&curves, &pane, min_x, max_x) {
+                add_plot_line(plot_ui, line);
+            }
+        });
+    }
+}
+
+//bounds fitting `lines`, with zero in view unless the output axis is logarithmic like the stacked panes
+fn stacked_pane_bounds(
+    lines: &[plot_image::PlotLine],
+    min_x: f64,
+    max_x: f64,
+    transform: &PlotTransform,
+) -> ([f64; 2], [f64; 2]) {
+    let (mut min_y, mut max_y) = match transform.units.log_y {
+        true => (f64::MAX, f64::MIN),
+        false => (0.0, 0.0),
+    };
+    for [_, y] in lines.iter().flat_map(|line| line.points.iter()) {
+        min_y = min_y.min(*y);
+        max_y = max_y.max(*y);
+    }
+    if min_y > max_y {
+        (min_y, max_y) = (0.0, 1.0);
+    }
+    let padding = match max_y > min_y {
+        true => (max_y - min_y) * 0.05,
+        false => 0.5,
+    };
+    return (
+        [
+            transform.x(transform.min_speed(min_x, max_x)),
+            min_y - padding,
+        ],
+        [transform.x(max_x), max_y + padding],
+    );
+}
+
+const CURRENT_CURVE_COLOR: egui::Color32 = egui::Color32::from_rgb(100, 100, 200);
+//the replay comparison and the convert mode candidate, both dashed
+const COMPARISON_CURVE_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 120, 60);
+const FIT_TARGET_COLOR: egui::Color32 = egui::Color32::from_rgb(190, 70, 150);
+const FIT_CURVE_COLORS: [egui::Color32; 4] = [
+    egui::Color32::from_rgb(60, 160, 90),
+    egui::Color32::from_rgb(60, 160, 190),
+    egui::Color32::from_rgb(170, 150, 40),
+    egui::Color32::from_rgb(120, 90, 200),
+];
+
+fn add_image_export_window(rawaccel_convert_gui: &mut RawaccelConvertGui, ctx: &egui::Context) {
+    let mut open = rawaccel_convert_gui.image_export.open;
+    egui::Window::new("Export Image")
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            let image_export = &mut rawaccel_convert_gui.image_export;
+            egui::Grid::new("image_export_grid").show(ui, |ui| {
+                ui.add(egui::Label::new("File Name").selectable(false));
+                ui.add(
+                    egui::TextEdit::singleline(&mut image_export.file_name).desired_width(160.0),
+                );
+                ui.end_row();
+
+                ui.add(egui::Label::new("Size (px)").selectable(false));
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut image_export.width).speed(10.0));
+                    ui.label("×");
+                    ui.add(egui::DragValue::new(&mut image_export.height).speed(10.0));
+                });
+                ui.end_row();
+            });
+            image_export.width = image_export.width.clamp(200, plot_image::MAX_PNG_SIZE);
+            image_export.height = image_export.height.clamp(200, plot_image::MAX_PNG_SIZE);
+            ui.label("Lines, legend and axes are drawn as shown on the plot in the current units.");
+
+            let mut save = None;
+            ui.horizontal(|ui| {
+                if ui.button("Save SVG").clicked() {
+                    save = Some("svg");
+                }
+                if ui.button("Save PNG").clicked() {
+                    save = Some("png");
+                }
+            });
+            if let Some(extension) = save {
+                let image = plot_image(rawaccel_convert_gui);
+                let image_export = &rawaccel_convert_gui.image_export;
+                let path = format!("{}.{}", image_export.file_name, extension);
+                let contents = match extension {
+                    "svg" => Ok(plot_image::to_svg(
+                        &image,
+                        image_export.width as f32 / image_export.height as f32,
+                    )
+                    .into_bytes()),
+                    _ => plot_image::to_png(&image, image_export.width, image_export.height),
+                };
+                rawaccel_convert_gui.image_export.status = Some(
+                    contents
+                        .and_then(|contents| file_io::save_file(&path, &contents))
+                        .map(|_| format!("Saved {}", path)),
+                );
+            }
+
+            match &rawaccel_convert_gui.image_export.status {
+                Some(Ok(ok)) => {
+                    ui.label(ok);
+                }
+                Some(Err(err)) => {
+                    ui.colored_label(ui.visuals().error_fg_color, err);
+                }
+                None => {}
+            }
+        });
+    rawaccel_convert_gui.image_export.open = open;
+}
+
+fn add_script_export_window(rawaccel_convert_gui: &mut RawaccelConvertGui, ctx: &egui::Context) {
+    let mut open = rawaccel_convert_gui.script_export.open;
+    egui::Window::new("Export Script")
+        .open(&mut open)
+        .default_width(420.0)
+        .show(ctx, |ui| {
+            let script_export = &mut rawaccel_convert_gui.script_export;
+            ui.horizontal(|ui| {
+                for format in [
+                    ScriptFormat::Gnuplot,
+                    ScriptFormat::Matplotlib,
+                    ScriptFormat::Desmos,
+                ] {
+                    ui.radio_value(&mut script_export.format, format, format.label());
+                }
+            });
+            egui::Grid::new("script_export_grid").show(ui, |ui| {
+                ui.add(egui::Label::new("File Name").selectable(false));
+                ui.add(
+                    egui::TextEdit::singleline(&mut script_export.file_name).desired_width(160.0),
+                );
+                ui.end_row();
+            });
+
+            if rawaccel_convert_gui.curve_error.is_some() {
+                ui.colored_label(
+                    ui.visuals().error_fg_color,
+                    "Fix the curve before exporting it",
+                );
+                return;
+            }
+            let generated_for = Some((
+                rawaccel_convert_gui.accel_args.clone(),
+                script_export.format,
+            ));
+            if script_export.generated_for != generated_for {
+                script_export.script =
+                    script_export::script(&rawaccel_convert_gui.accel_args, script_export.format);
+                script_export.closed_form =
+                    script_export::has_closed_form(&rawaccel_convert_gui.accel_args);
+                script_export.generated_for = generated_for;
+                script_export.status = None;
+            }
+
+            match (script_export.closed_form, script_export.format) {
+                (true, _) => ui.label("Sensitivity as a closed form with the current values."),
+                (false, ScriptFormat::Matplotlib) => {
+                    ui.label("No closed form for this curve, only the sampled points are plotted.")
+                }
+                (false, _) => {
+                    ui.label("No closed form for this curve, exported as a table of points.")
+                }
+            };
+            if script_export.format == ScriptFormat::Desmos {
+                ui.label("Paste into the Desmos expression list.");
+            }
+
+            egui::ScrollArea::vertical()
+                .max_height(240.0)
+                .show(ui, |ui| {
+                    ui.add_sized(
+                        [ui.available_width(), 1.0],
+                        egui::TextEdit::multiline(&mut script_export.script.as_str()).code_editor(),
+                    );
+                });
+
+            ui.horizontal(|ui| {
+                if ui.button("Copy").clicked() {
+                    ui.output_mut(|output| output.copied_text = script_export.script.clone());
+                }
+                if ui.button("Save").clicked() {
+                    let path = format!(
+                        "{}.{}",
+                        script_export.file_name,
+                        script_export.format.extension()
+                    );
+                    script_export.status = Some(
+                        file_io::save_file(&path, script_export.script.as_bytes())
+                            .map(|_| format!("Saved {}", path)),
+                    );
+                }
+            });
+
+            match &script_export.status {
+                Some(Ok(ok)) => {
+                    ui.label(ok);
+                }
+                Some(Err(err)) => {
+                    ui.colored_label(ui.visuals().error_fg_color, err);
+                }
+                None => {}
+            }
+        });
+    rawaccel_convert_gui.script_export.open = open;
+}
+
+//the central plot as it is currently shown, one pane per view when stacked
+fn plot_image(rawaccel_convert_gui: &mut RawaccelConvertGui) -> plot_image::PlotImage {
+    let plot_accel_args = rawaccel_convert_gui.accel_args.clone();
+    let plot_bounds = get_bounds(rawaccel_convert_gui, &plot_accel_args);
+    let curve_valid = rawaccel_convert_gui.curve_error.is_none() && plot_bounds.is_ok();
+    let plot_bounds = plot_bounds.unwrap_or_else(|_| fallback_bounds(&plot_accel_args));
+    let transform = plot_transform(rawaccel_convert_gui);
+    let x_label = format!("Input Speed ({})", transform.units.input.label());
+
+    if rawaccel_convert_gui.stacked_plots.enabled {
+        let curves = plotted_curves(rawaccel_convert_gui);
+        let panes = stacked_panes(&rawaccel_convert_gui.stacked_plots, &transform)
+            .into_iter()
+            .map(|pane| {
+                let lines = stacked_pane_lines(&curves, &pane, 0.0, plot_bounds.1[0]);
+                plot_image::ImagePane {
+                    transform: pane.transform,
+                    bounds: stacked_pane_bounds(&lines, 0.0, plot_bounds.1[0], &pane.transform),
+                    x_label: x_label.clone(),
+                    y_label: pane.label,
+                    secondary_axes: false,
+                    areas: vec![],
+                    lines,
+                }
+            })
+            .collect();
+        return plot_image::PlotImage { panes };
+    }
+
+    let current_points = cached_plot_samples(
+        &mut rawaccel_convert_gui.plot_samples_cache,
+        &plot_accel_args,
+        plot_bounds.0[0],
+        plot_bounds.1[0],
+        &transform,
+    );
+    let transformed_bounds = transform_bounds(plot_bounds, &current_points, &transform);
+    let (areas, lines) = central_plot_items(
+        rawaccel_convert_gui,
+        &plot_accel_args,
+        plot_bounds,
+        curve_valid.then_some(current_points),
+        transformed_bounds,
+        &transform,
+    );
+    return plot_image::PlotImage {
+        panes: vec![plot_image::ImagePane {
+            transform,
+            bounds: transformed_bounds,
+            x_label,
+            y_label: transform.units.output.label().to_string(),
+            secondary_axes: true,
+            areas,
+            lines,
+        }],
+    };
+}
+
+//a curve on the plot with how it is drawn
+struct PlottedCurve {
+    name: String,
+    args: AccelArgs,
+    color: egui::Color32,
+    style: plot_image::LineStyle,
+}
+
+//every curve on the plot with its name in the legend
+fn plotted_curves(rawaccel_convert_gui: &RawaccelConvertGui) -> Vec<PlottedCurve> {
+    let plotted_curve = |name: String, args: AccelArgs, color: egui::Color32, style| PlottedCurve {
+        name,
+        args,
+        color,
+        style,
+    };
+    let mut curves = vec![];
+    if rawaccel_convert_gui.curve_error.is_none() {
+        curves.push(plotted_curve(
+            "Current".to_string(),
+            rawaccel_convert_gui.accel_args.clone(),
+            CURRENT_CURVE_COLOR,
+            plot_image::LineStyle::Solid,
+        ));
+    }
+    if let Some(comparison) = &rawaccel_convert_gui.replay.comparison {
+        curves.push(plotted_curve(
+            format!(
+                "Comparison: {}",
+                fit::mode_label(&comparison.mode, comparison.gain)
+            ),
+            comparison.clone(),
+            COMPARISON_CURVE_COLOR,
+            plot_image::LineStyle::Dashed,
+        ));
+    }
+    let curve_fit = &rawaccel_convert_gui.curve_fit;
+    if curve_fit.open {
+        for (i, mode_fit) in curve_fit.fits.iter().enumerate() {
+            if curve_fit.shown_fits[i] {
+                curves.push(plotted_curve(
+                    mode_fit.label.clone(),
+                    mode_fit.result.args.clone(),
+                    fit_color(i),
+                    plot_image::LineStyle::Solid,
+                ));
+            }
+        }
+    }
+    let convert_mode = &rawaccel_convert_gui.convert_mode;
+    if let (true, Some(candidate)) = (convert_mode.open, &convert_mode.candidate) {
+        curves.push(plotted_curve(
+            format!("After: {}", candidate.label),
+            candidate.result.args.clone(),
+            COMPARISON_CURVE_COLOR,
+            plot_image::LineStyle::Dashed,
+        ));
+    }
+    let sweep = &rawaccel_convert_gui.sweep;
+    if let (true, Some(parameter)) = (
+        sweep.open,
+        parameters::all_parameters()
+            .into_iter()
+            .find(|parameter| parameter.id == sweep.parameter_id),
+    ) {
+        for (i, curve) in sweep.curves.iter().enumerate() {
+            let mut sweep_args = rawaccel_convert_gui.accel_args.clone();
+            (parameter.set)(&mut sweep_args, curve.value);
+            curves.push(plotted_curve(
+                format!(
+                    "{} = {}",
+                    parameter.label,
+                    fit::format_parameter(curve.value)
+                ),
+                sweep_args,
+                sweep_curve_color(sweep, i),
+                plot_image::LineStyle::Solid,
+            ));
+        }
+    }
+    return curves;
+}
+
+fn add_hover_inspector(ui: &mut egui::Ui, curves: &[PlottedCurve], speed: f64, dpi: u32) {
+    //physical speeds need the dpi
+    let with_physical = |counts_per_ms: f64| -> String {
+        match dpi {
+            0 => format!("{:.2} counts/ms", counts_per_ms),
+            _ => format!(
+                "{:.2} counts/ms ({:.1} {})",
+                counts_per_ms,
+                units::InputUnit::CmPerS.speed_from_counts_per_ms(counts_per_ms, dpi),
+                units::InputUnit::CmPerS.label()
+            ),
+        }
+    };
+    ui.label(format!("Input Speed: {}", with_physical(speed)));
+    egui::Grid::new("hover_inspector_grid")
+        .striped(true)
+        .show(ui, |ui| {
+            ui.strong("Curve");
+            ui.strong("Sens");
+            ui.strong("Velocity");
+            ui.strong("Gain");
+            ui.end_row();
+            for curve in curves {
+                ui.label(&curve.name);
+                match curve_analysis::readout(&curve.args, speed) {
+                    Some(readout) => {
+                        ui.label(format!("{:.4}", readout.sens));
+                        ui.label(with_physical(readout.velocity));
+                        ui.label(format!("{:.4}", readout.gain));
+                    }
+                    None => {
+                        ui.colored_label(ui.visuals().error_fg_color, "Invalid");
+                        ui.label("");
+                        ui.label("");
+                    }
+                }
+                ui.end_row();
+            }
+        });
+}
+
+//how close in points the pointer has to be to grab a handle
+const HANDLE_GRAB_DISTANCE: f32 = 10.0;
+
+//markers for `parameters::handles`, dragging one moves its parameters and the curve regenerates from them
+fn add_parameter_handles(
+    rawaccel_convert_gui: &mut RawaccelConvertGui,
+    plot_accel_args: &AccelArgs,
+    transform: &PlotTransform,
+    plot_ui: &mut egui_plot::PlotUi,
+) {
+    let handles = parameters::handles(plot_accel_args);
+    let positions = handles
+        .iter()
+        .map(|handle| {
+            try_get_point(handle.speed, plot_accel_args)
+                .ok()
+                .map(|y| transform.point(handle.speed, y))
+                .filter(|[x, y]| x.is_finite() && y.is_finite())
+        })
+        .collect::<Vec<Option<[f64; 2]>>>();
+    let response = plot_ui.response().clone();
+    //the handle under the pointer, closest first
+    let pointer_handle = response.hover_pos().and_then(|pointer| {
+        handles
+            .iter()
+            .zip(&positions)
+            .filter_map(|(handle, position)| {
+                let position = (*position)?;
+                let distance = plot_ui
+                    .screen_from_plot(egui_plot::PlotPoint::new(position[0], position[1]))
+                    .distance(pointer);
+                (distance <= HANDLE_GRAB_DISTANCE).then_some((handle.label, distance))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(label, _)| label)
+    });
+    if response.drag_started() {
+        rawaccel_convert_gui.dragged_handle = pointer_handle;
+    }
+    if !response.dragged() {
+        rawaccel_convert_gui.dragged_handle = None;
+    }
+    let active_handle = rawaccel_convert_gui.dragged_handle.or(pointer_handle);
+
+    for (handle, position) in handles.iter().zip(&positions) {
+        let position = match position {
+            Some(some) => *some,
+            None => continue,
+        };
+        let active = active_handle == Some(handle.label);
+        plot_ui.points(
+            egui_plot::Points::new(vec![position])
+                .shape(egui_plot::MarkerShape::Circle)
+                .filled(true)
+                .radius(if active { 7.0 } else { 5.0 })
+                .color(egui::Color32::from_rgb(230, 150, 40)),
+        );
+        if active {
+            plot_ui.text(
+                egui_plot::Text::new(
+                    egui_plot::PlotPoint::new(position[0], position[1]),
+                    format!("  {}", handle.label),
+                )
+                .anchor(egui::Align2::LEFT_BOTTOM),
+            );
+        }
+    }
+
+    let (handle, pointer) = match (
+        handles
+            .iter()
+            .find(|handle| Some(handle.label) == rawaccel_convert_gui.dragged_handle),
+        plot_ui.pointer_coordinate(),
+    ) {
+        (Some(handle), Some(pointer)) => (handle, pointer),
+        _ => return,
+    };
+    //the handle only follows the pointer sideways when it has a speed parameter
+    let mut speed = handle.speed;
+    if let Some(parameter) = handle.speed_parameter {
+        let pointer_speed = transform.inverse_x(pointer.x).max(0.0);
+        if parameter.is_valid(pointer_speed) {
+            apply_parameter_value(rawaccel_convert_gui, parameter, pointer_speed);
+            speed = pointer_speed;
+        }
+    }
+    if let Some(parameter) = handle.scale_parameter {
+        let pointer_sens = match transform.units.output {
+            OutputUnit::Velocity => transform.inverse_y(pointer.y) / speed,
+            OutputUnit::Sens | OutputUnit::Cm360 => transform.inverse_y(pointer.y),
+        };
+        let handle_sens = get_point(handle.speed, &sens_args(plot_accel_args));
+        let value = (parameter.get)(plot_accel_args) * pointer_sens / handle_sens;
+        if handle_sens > 0.0 && parameter.is_valid(value) {
+            apply_parameter_value(rawaccel_convert_gui, parameter, value);
+        }
+    }
+}
+
+fn record_motion(
+    rawaccel_convert_gui: &mut RawaccelConvertGui,
+    ui: &mut egui::Ui,
+    rect: egui::Rect,
+) {
+    //timestamps only advance when frames are drawn
+    ui.ctx().request_repaint();
+
+    let (in_capture_area, time, raw_deltas, pointer_delta) = ui.input(|i| {
+        let raw_deltas = i
+            .raw
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                egui::Event::MouseMoved(delta) => Some(*delta),
+                _ => None,
+            })
+            .collect::<Vec<egui::Vec2>>();
+        (
+            i.pointer
+                .hover_pos()
+                .is_some_and(|position| rect.contains(position)),
+            i.time,
+            raw_deltas,
+            i.pointer.delta() * i.pixels_per_point(),
+        )
+    });
+    if !in_capture_area {
+        return;
+    }
+
+    let time_ms = time * 1000.0;
+    if !raw_deltas.is_empty() {
+        for delta in raw_deltas {
+            rawaccel_convert_gui
+                .motion_recording
+                .push(time_ms, delta.x as f64, delta.y as f64);
+        }
+    } else if pointer_delta != egui::Vec2::ZERO {
+        rawaccel_convert_gui.recorder.used_pointer_fallback = true;
+        rawaccel_convert_gui.motion_recording.push(
+            time_ms,
+            pointer_delta.x as f64,
+            pointer_delta.y as f64,
+        );
+    }
+}
+
+fn load_motion_recording(
+    rawaccel_convert_gui: &mut RawaccelConvertGui,
+    loaded: Result<Vec<u8>, String>,
+) {
+    let recording = loaded.and_then(|bytes| {
+        let text = String::from_utf8_lossy(&bytes);
+        match libinput_log::is_libinput_log(&text) {
+            true => libinput_log::parse_libinput_log(&text),
+            false => motion::MotionRecording::from_csv(&text),
+        }
+    });
+    rawaccel_convert_gui.recorder.status = Some(match recording {
+        Ok(ok) => {
+            rawaccel_convert_gui.motion_recording = ok;
+            rawaccel_convert_gui.recorder.recording = false;
+            rawaccel_convert_gui.recorder.used_pointer_fallback = false;
+            Ok("Loaded".to_string())
+        }
+        Err(err) => Err(err),
+    });
+}
+
+fn speed_histogram_areas(
+    recording: &MotionRecording,
+    max_speed: f64,
+    transformed_bounds: ([f64; 2], [f64; 2]),
+    transform: &PlotTransform,
+) -> Vec<plot_image::PlotArea> {
+    let samples = recording.speed_samples();
+    if samples.is_empty() {
+        return vec![];
+    }
+
+    let bins = motion::speed_histogram(&samples, max_speed, 40);
+    let max_fraction = bins.iter().fold(0.0, |max: f64, bin| max.max(bin.fraction));
+    let bottom = transformed_bounds.0[1];
+    //tallest bar fills most of the visible height, bars only show relative usage
+    let scale = (transformed_bounds.1[1] - bottom) * 0.8 / max_fraction;
+    let color = egui::Color32::from_gray(128);
+    return bins
+        .iter()
+        .map(|bin| plot_image::PlotArea {
+            name: "Speed Distribution".to_string(),
+            fill: color.linear_multiply(0.2),
+            stroke: color,
+            //bars reaching zero start at the edge of a log axis
+            min: [
+                transform.x(bin.min_speed).max(transformed_bounds.0[0]),
+                bottom,
+            ],
+            max: [transform.x(bin.max_speed), bottom + bin.fraction * scale],
+        })
+        .collect();
+}
+
+fn decreasing_velocity_areas(
+    ranges: &[curve_analysis::SpeedRange],
+    transformed_bounds: ([f64; 2], [f64; 2]),
+    transform: &PlotTransform,
+) -> Vec<plot_image::PlotArea> {
+    return ranges
+        .iter()
+        .map(|range| plot_image::PlotArea {
+            name: "Velocity Decreasing".to_string(),
+            fill: egui::Color32::from_rgba_unmultiplied(220, 60, 60, 40),
+            stroke: egui::Color32::TRANSPARENT,
+            min: [
+                transform.x(range.start).max(transformed_bounds.0[0]),
+                transformed_bounds.0[1],
+            ],
+            max: [transform.x(range.end), transformed_bounds.1[1]],
+        })
+        .collect();
+}
+
+//everything drawn on the central plot apart from the parameter handles, exported images draw the same
+fn central_plot_items(
+    rawaccel_convert_gui: &mut RawaccelConvertGui,
+    plot_accel_args: &AccelArgs,
+    plot_bounds: ([f64; 2], [f64; 2]),
+    current_points: Option<Vec<[f64; 2]>>,
+    transformed_bounds: ([f64; 2], [f64; 2]),
+    transform: &PlotTransform,
+) -> (Vec<plot_image::PlotArea>, Vec<plot_image::PlotLine>) {
+    let mut areas = vec![];
+    if rawaccel_convert_gui.recorder.show_histogram {
+        areas.extend(speed_histogram_areas(
+            &rawaccel_convert_gui.motion_recording,
+            plot_bounds.1[0],
+            transformed_bounds,
+            transform,
+        ));
+    }
+    areas.extend(decreasing_velocity_areas(
+        &rawaccel_convert_gui.decreasing_velocity_ranges,
+        transformed_bounds,
+        transform,
+    ));
+
+    let mut lines = sweep_lines(&rawaccel_convert_gui.sweep, transform);
+    if let Some(current_points) = current_points {
+        lines.push(plot_image::PlotLine {
+            name: "Current".to_string(),
+            color: CURRENT_CURVE_COLOR,
+            style: plot_image::LineStyle::Solid,
+            points: current_points,
+        });
+    }
+    let plot_samples_cache = &mut rawaccel_convert_gui.plot_samples_cache;
+    lines.extend(curve_fit_lines(
+        &rawaccel_convert_gui.curve_fit,
+        plot_samples_cache,
+        plot_accel_args,
+        plot_bounds,
+        transform,
+    ));
+    lines.extend(convert_mode_lines(
+        &rawaccel_convert_gui.convert_mode,
+        plot_samples_cache,
+        plot_accel_args,
+        plot_bounds,
+        transform,
+    ));
+    lines.extend(comparison_lines(
+        &rawaccel_convert_gui.replay,
+        plot_samples_cache,
+        plot_accel_args,
+        plot_bounds,
+        transform,
+    ));
+    return (areas, lines);
+}
+
+fn add_plot_line(plot_ui: &mut egui_plot::PlotUi, line: plot_image::PlotLine) {
+    let points = egui_plot::PlotPoints::new(line.points);
+    match line.style {
+        plot_image::LineStyle::Solid => {
+            plot_ui.line(
+                egui_plot::Line::new(points)
+                    .color(line.color)
+                    .name(line.name),
+            );
+        }
+        plot_image::LineStyle::Dashed => {
+            plot_ui.line(
+                egui_plot::Line::new(points)
+                    .color(line.color)
+                    .style(egui_plot::LineStyle::dashed_loose())
+                    .name(line.name),
+            );
+        }
+        plot_image::LineStyle::Markers => {
+            plot_ui.points(
+                egui_plot::Points::new(points)
+                    .radius(3.0)
+                    .color(line.color)
+                    .name(line.name),
+            );
+        }
+    }
+}
+
+fn add_plot_area(plot_ui: &mut egui_plot::PlotUi, area: plot_image::PlotArea) {
+    let stroke = match area.stroke == egui::Color32::TRANSPARENT {
+        true => egui::Stroke::NONE,
+        false => egui::Stroke::new(1.0, area.stroke),
+    };
+    plot_ui.polygon(
+        egui_plot::Polygon::new(egui_plot::PlotPoints::new(vec![
+            [area.min[0], area.min[1]],
+            [area.max[0], area.min[1]],
+            [area.max[0], area.max[1]],
+            [area.min[0], area.max[1]],
+        ]))
+        .fill_color(area.fill)
+        .stroke(stroke)
+        .name(area.name),
+    );
+}
+
+fn sens_args(args: &AccelArgs) -> AccelArgs {
+    let mut sens_args = args.clone();
+    sens_args.point_scaling = PointScaling::Sens;
+    return sens_args;
+}
+
+fn unselectable_warn_if_debug_build(ui: &mut egui::Ui) {
+    if cfg!(debug_assertions) {
+        ui.add(
+            egui::Label::new(
+                egui::RichText::new("⚠ Debug build ⚠")
+                    .small()
+                    .color(ui.visuals().warn_fg_color),
+            )
+            .selectable(false),
+        );
+    }
+}
+
+fn add_number_format(number_format: &mut NumberFormat, ui: &mut egui::Ui) {
+    ui.label("Decimal Separator");
+    for decimal_separator in [DecimalSeparator::Point, DecimalSeparator::Comma] {
+        ui.radio_value(
+            &mut number_format.decimal_separator,
+            decimal_separator,
+            decimal_separator.label(),
+        );
+    }
+    ui.label(
+        egui::RichText::new("Applies to exported points, typed values always accept both a decimal point and a decimal comma")
+            .weak(),
+    );
+    ui.separator();
+
+    ui.label("Field Separator");
+    for field_separator in [
+        FieldSeparator::Comma,
+        FieldSeparator::Semicolon,
+        FieldSeparator::Tab,
+        FieldSeparator::Space,
+    ] {
+        ui.radio_value(
+            &mut number_format.field_separator,
+            field_separator,
+            field_separator.label(),
+        );
+    }
+    if number_format.is_ambiguous() {
+        ui.colored_label(
+            ui.visuals().warn_fg_color,
+            "⚠ Decimal and field separators are the same",
+        );
+    }
+    ui.separator();
+
+    ui.label("Precision");
+    let mut digits = match number_format.precision {
+        Precision::Full => 6,
+        Precision::Decimals(digits) | Precision::SignificantDigits(digits) => digits,
+    };
+    ui.radio_value(&mut number_format.precision, Precision::Full, "Full");
+    if ui
+        .radio(
+            matches!(number_format.precision, Precision::Decimals(_)),
+            "Fixed Decimals",
+        )
+        .clicked()
+    {
+        number_format.precision = Precision::Decimals(digits);
+    }
+    if ui
+        .radio(
+            matches!(number_format.precision, Precision::SignificantDigits(_)),
+            "Significant Digits",
+        )
+        .clicked()
+    {
+        number_format.precision = Precision::SignificantDigits(digits.max(1));
+    }
+    if number_format.precision != Precision::Full {
+        ui.add(egui::DragValue::new(&mut digits).speed(0.1));
+        number_format.precision = match number_format.precision {
+            Precision::Decimals(_) => Precision::Decimals(digits.min(17)),
+            _ => Precision::SignificantDigits(digits.clamp(1, 17)),
+        };
     }
 }
 
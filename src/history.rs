@@ -0,0 +1,159 @@
+const MAX_HISTORY_LENGTH: usize = 256;
+
+pub struct History<T> {
+    undo_stack: Vec<T>,
+    redo_stack: Vec<T>,
+    current: Option<T>,
+    last_edit_id: Option<egui::Id>,
+}
+
+impl<T> Default for History<T> {
+    fn default() -> Self {
+        Self {
+            undo_stack: vec![],
+            redo_stack: vec![],
+            current: None,
+            last_edit_id: None,
+        }
+    }
+}
+
+impl<T: Clone + PartialEq> History<T> {
+    //called once per frame with the current state and the widget that has focus
+    //consecutive changes made while the same widget keeps focus are merged into one step
+    pub fn record(&mut self, state: &T, edit_id: Option<egui::Id>) {
+        let previous = match &self.current {
+            Some(some) => some,
+            None => {
+                self.current = Some(state.clone());
+                return;
+            }
+        };
+
+        if previous == state {
+            if self.last_edit_id != edit_id {
+                self.last_edit_id = None;
+            }
+            return;
+        }
+
+        let coalesce = edit_id.is_some() && self.last_edit_id == edit_id;
+        if !coalesce {
+            self.undo_stack.push(previous.clone());
+            if self.undo_stack.len() > MAX_HISTORY_LENGTH {
+                self.undo_stack.remove(0);
+            }
+        }
+        self.redo_stack.clear();
+        self.last_edit_id = edit_id;
+        self.current = Some(state.clone());
+    }
+
+    pub fn undo(&mut self, state: &T) -> Option<T> {
+        let restored = self.undo_stack.pop()?;
+        self.redo_stack.push(state.clone());
+        self.current = Some(restored.clone());
+        self.last_edit_id = None;
+        return Some(restored);
+    }
+
+    pub fn redo(&mut self, state: &T) -> Option<T> {
+        let restored = self.redo_stack.pop()?;
+        self.undo_stack.push(state.clone());
+        self.current = Some(restored.clone());
+        self.last_edit_id = None;
+        return Some(restored);
+    }
+
+    pub fn can_undo(&self) -> bool {
+        return !self.undo_stack.is_empty();
+    }
+
+    pub fn can_redo(&self) -> bool {
+        return !self.redo_stack.is_empty();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recorded(states: &[(i32, Option<egui::Id>)]) -> History<i32> {
+        let mut history = History::default();
+        for (state, edit_id) in states {
+            history.record(state, *edit_id);
+        }
+        return history;
+    }
+
+    #[test]
+    fn same_widget_edits_coalesce() {
+        let field = Some(egui::Id::new("field"));
+        let mut history = recorded(&[(0, None), (1, field), (12, field), (123, field)]);
+        assert_eq!(history.undo(&123), Some(0));
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn different_widgets_are_separate_steps() {
+        let first = Some(egui::Id::new("first"));
+        let second = Some(egui::Id::new("second"));
+        let mut history = recorded(&[(0, None), (1, first), (2, second)]);
+        assert_eq!(history.undo(&2), Some(1));
+        assert_eq!(history.undo(&1), Some(0));
+    }
+
+    #[test]
+    fn unfocused_edits_are_separate_steps() {
+        let mut history = recorded(&[(0, None), (1, None), (2, None)]);
+        assert_eq!(history.undo(&2), Some(1));
+        assert_eq!(history.undo(&1), Some(0));
+    }
+
+    #[test]
+    fn refocusing_starts_a_new_step() {
+        let field = Some(egui::Id::new("field"));
+        let mut history = recorded(&[(0, None), (1, field), (1, None), (2, field)]);
+        assert_eq!(history.undo(&2), Some(1));
+        assert_eq!(history.undo(&1), Some(0));
+    }
+
+    #[test]
+    fn undo_redo_round_trip() {
+        let mut history = recorded(&[(0, None), (1, None), (2, None)]);
+        assert_eq!(history.undo(&2), Some(1));
+        assert_eq!(history.undo(&1), Some(0));
+        assert_eq!(history.undo(&0), None);
+        assert_eq!(history.redo(&0), Some(1));
+        assert_eq!(history.redo(&1), Some(2));
+        assert_eq!(history.redo(&2), None);
+        assert_eq!(history.undo(&2), Some(1));
+    }
+
+    #[test]
+    fn new_edit_clears_redo() {
+        let mut history = recorded(&[(0, None), (1, None)]);
+        assert_eq!(history.undo(&1), Some(0));
+        assert!(history.can_redo());
+        history.record(&0, None);
+        history.record(&5, None);
+        assert!(!history.can_redo());
+        assert_eq!(history.undo(&5), Some(0));
+    }
+
+    #[test]
+    fn history_length_is_capped() {
+        let mut history = History::default();
+        for state in 0..(MAX_HISTORY_LENGTH + 10) as i32 {
+            history.record(&state, None);
+        }
+        let mut state = (MAX_HISTORY_LENGTH + 9) as i32;
+        let mut steps = 0;
+        while let Some(restored) = history.undo(&state) {
+            state = restored;
+            steps += 1;
+        }
+        assert_eq!(steps, MAX_HISTORY_LENGTH);
+        assert_eq!(state, 9);
+    }
+}
@@ -0,0 +1,226 @@
+use crate::motion::MotionRecording;
+
+const EV_SYN: i64 = 0;
+const EV_REL: i64 = 2;
+const SYN_REPORT: i64 = 0;
+const REL_X: i64 = 0;
+const REL_Y: i64 = 1;
+
+fn is_libinput_record(text: &str) -> bool {
+    return text.contains("evdev:");
+}
+
+fn is_debug_events(text: &str) -> bool {
+    return text.contains("POINTER_MOTION");
+}
+
+pub fn is_libinput_log(text: &str) -> bool {
+    return is_libinput_record(text) || is_debug_events(text);
+}
+
+//accepts the yaml written by `libinput record` and the text printed by `libinput debug-events`
+pub fn parse_libinput_log(text: &str) -> Result<MotionRecording, String> {
+    let recording = if is_libinput_record(text) {
+        parse_libinput_record(text)
+    } else if is_debug_events(text) {
+        parse_debug_events(text)
+    } else {
+        return Err("Not a libinput record or debug-events log".to_string());
+    };
+
+    if recording.events.is_empty() {
+        return Err("Log contains no relative pointer motion".to_string());
+    }
+    return Ok(recording);
+}
+
+//event lines look like `- [  3, 164321,   2,   0,      -2] # EV_REL / REL_X  -2`
+fn parse_libinput_record(text: &str) -> MotionRecording {
+    let mut recording = MotionRecording::default();
+    let mut dx = 0.0;
+    let mut dy = 0.0;
+    for line in text.lines() {
+        let line = line.trim_start().trim_start_matches('-').trim_start();
+        let fields = match line
+            .strip_prefix('[')
+            .and_then(|line| line.split(']').next())
+        {
+            Some(some) => some,
+            None => continue,
+        };
+        let values = match fields
+            .split(',')
+            .map(|value| value.trim().parse::<i64>())
+            .collect::<Result<Vec<i64>, _>>()
+        {
+            Ok(ok) => ok,
+            Err(_) => continue,
+        };
+        let [seconds, microseconds, event_type, code, value] = match values[..] {
+            [a, b, c, d, e] => [a, b, c, d, e],
+            _ => continue,
+        };
+
+        match (event_type, code) {
+            (EV_REL, REL_X) => dx += value as f64,
+            (EV_REL, REL_Y) => dy += value as f64,
+            (EV_SYN, SYN_REPORT) => {
+                if dx != 0.0 || dy != 0.0 {
+                    let time_ms = seconds as f64 * 1000.0 + microseconds as f64 / 1000.0;
+                    recording.push(time_ms, dx, dy);
+                }
+                dx = 0.0;
+                dy = 0.0;
+            }
+            _ => {}
+        }
+    }
+    return recording;
+}
+
+//motion lines look like ` event5   POINTER_MOTION   +3.456s	 -0.65/ -0.65 ( -1.00/ -1.00)`
+//the deltas in brackets are unaccelerated
+fn parse_debug_events(text: &str) -> MotionRecording {
+    let mut recording = MotionRecording::default();
+    for line in text.lines() {
+        if !line.contains("POINTER_MOTION") {
+            continue;
+        }
+        let time_seconds = line.split_whitespace().find_map(|token| {
+            token
+                .strip_prefix('+')
+                .and_then(|token| token.strip_suffix('s'))
+                .and_then(|token| token.parse::<f64>().ok())
+        });
+        let unaccelerated = line
+            .split_once('(')
+            .and_then(|(_, rest)| rest.split_once(')'))
+            .and_then(|(deltas, _)| deltas.split_once('/'))
+            .and_then(
+                |(dx, dy)| match (dx.trim().parse::<f64>(), dy.trim().parse::<f64>()) {
+                    (Ok(dx), Ok(dy)) => Some((dx, dy)),
+                    _ => None,
+                },
+            );
+        if let (Some(time_seconds), Some((dx, dy))) = (time_seconds, unaccelerated) {
+            recording.push(time_seconds * 1000.0, dx, dy);
+        }
+    }
+    return recording;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::motion;
+
+    const RECORD: &str = "version: 1
+ndevices: 1
+libinput:
+  version: \"1.25.0\"
+devices:
+- node: /dev/input/event7
+  evdev:
+    name: \"Logitech G Pro\"
+  events:
+  # Current time is 12:34:56
+  - evdev:
+    - [  0,      0,   2,   0,       1] # EV_REL / REL_X                  1
+    - [  0,      0,   2,   1,      -1] # EV_REL / REL_Y                 -1
+    - [  0,      0,   0,   0,       0] # ------------ SYN_REPORT (0) ---------- +0ms
+  - evdev:
+    - [  0,   1000,   2,   0,       4] # EV_REL / REL_X                  4
+    - [  0,   1000,   0,   0,       0] # ------------ SYN_REPORT (0) ---------- +1ms
+  - evdev:
+    - [  0,   2000,   4,   4,  589825] # EV_MSC / MSC_SCAN         589825
+    - [  0,   2000,   0,   0,       0] # ------------ SYN_REPORT (0) ---------- +1ms
+  - evdev:
+    - [  0,   3000,   2,   1,      -6] # EV_REL / REL_Y                 -6
+    - [  0,   3000,   2,   0,       8] # EV_REL / REL_X                  8
+    - [  0,   3000,   0,   0,       0] # ------------ SYN_REPORT (0) ---------- +1ms
+  - evdev:
+    - [  1,      0,   2,   0,       2] # EV_REL / REL_X                  2
+    - [  1,      0,   0,   0,       0] # ------------ SYN_REPORT (0) ---------- +997ms
+";
+
+    const DEBUG_EVENTS: &str = "-event7   DEVICE_ADDED                 Logitech G Pro                    seat0 default group1  cap:p left scroll-nat scroll-button
+ event7   POINTER_MOTION               +1.000s	  1.50/ -1.50 (  1.00/ -1.00)
+ event7   POINTER_MOTION               +1.001s	  6.00/  0.00 ( +4.00/ +0.00)
+ event7   POINTER_BUTTON               +1.002s	BTN_LEFT (272) pressed, seat count: 1
+ event7   POINTER_MOTION               +1.003s	 12.00/ -9.00 (  8.00/ -6.00)
+";
+
+    fn events(recording: &MotionRecording) -> Vec<(f64, f64, f64)> {
+        return recording
+            .events
+            .iter()
+            .map(|event| {
+                (
+                    (event.time_ms * 1000.0).round() / 1000.0,
+                    event.dx,
+                    event.dy,
+                )
+            })
+            .collect();
+    }
+
+    fn assert_close(value: f64, expected: f64) {
+        assert!((value - expected).abs() < 1e-9, "{} != {}", value, expected);
+    }
+
+    #[test]
+    fn detects_formats() {
+        assert!(is_libinput_record(RECORD));
+        assert!(is_debug_events(DEBUG_EVENTS));
+        assert!(!is_libinput_log("time_ms,dx,dy\n0,1,1\n"));
+    }
+
+    #[test]
+    fn parses_record() {
+        let recording = parse_libinput_log(RECORD).unwrap();
+        assert_eq!(
+            events(&recording),
+            vec![
+                (0.0, 1.0, -1.0),
+                (1.0, 4.0, 0.0),
+                (3.0, 8.0, -6.0),
+                (1000.0, 2.0, 0.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_debug_events() {
+        let recording = parse_libinput_log(DEBUG_EVENTS).unwrap();
+        assert_eq!(
+            events(&recording),
+            vec![(1000.0, 1.0, -1.0), (1001.0, 4.0, 0.0), (1003.0, 8.0, -6.0)]
+        );
+    }
+
+    #[test]
+    fn record_stats() {
+        let recording = parse_libinput_log(RECORD).unwrap();
+        //4 counts over 1ms, 10 counts over 2ms and 2 counts after a gap capped to 50ms
+        let stats = motion::motion_stats(&recording.speed_samples(), Some(3.0)).unwrap();
+        assert_close(stats.median_speed, 2.0 / 50.0);
+        assert_close(stats.p95_speed, 4.0);
+        assert_close(stats.time_above_cap.unwrap(), 3.0 / 53.0);
+    }
+
+    #[test]
+    fn debug_events_stats() {
+        let recording = parse_libinput_log(DEBUG_EVENTS).unwrap();
+        //4 counts over 1ms then 10 counts over 2ms
+        let stats = motion::motion_stats(&recording.speed_samples(), None).unwrap();
+        assert_close(stats.median_speed, 5.0);
+        assert_close(stats.p95_speed, 5.0);
+        assert_eq!(stats.time_above_cap, None);
+    }
+
+    #[test]
+    fn rejects_logs_without_motion() {
+        assert!(parse_libinput_log("event7 POINTER_MOTION nothing").is_err());
+        assert!(parse_libinput_log("hello").is_err());
+    }
+}
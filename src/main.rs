@@ -1,6 +1,23 @@
 #![windows_subsystem = "windows"]
 
+mod curve_analysis;
+mod dpi_change;
+mod expression;
+mod file_io;
+mod fit;
+mod games;
 mod gui;
+mod history;
+mod libinput_log;
+mod metrics;
+mod motion;
+mod number_format;
+mod parameters;
+mod plot_image;
+mod script_export;
+mod sweep;
+mod units;
+mod validation;
 
 #[cfg(not(target_arch = "wasm32"))]
 fn main() -> eframe::Result<()> {
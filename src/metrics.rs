@@ -0,0 +1,148 @@
+use rawaccel_convert::types::{AccelArgs, PointScaling};
+
+use crate::gui::{cap_speed, get_point};
+use crate::motion::SpeedSample;
+use crate::units;
+
+//summary of a curve weighted by how long the mouse actually spends at each speed
+pub struct UsageMetrics {
+    pub mean_sens: f64,
+    //5th and 95th percentile of the effective cm/360
+    pub cm_per_360_low: f64,
+    pub cm_per_360_high: f64,
+    //`None` when the curve has no cap
+    pub capped_fraction: Option<f64>,
+    pub decreasing_velocity_fraction: f64,
+}
+
+//`None` without motion or when the curve can't be evaluated at a recorded speed
+pub fn usage_metrics(
+    samples: &[SpeedSample],
+    args: &AccelArgs,
+    yaw: f64,
+    game_sens: f64,
+) -> Option<UsageMetrics> {
+    let total_duration: f64 = samples.iter().map(|sample| sample.duration_ms).sum();
+    if total_duration <= 0.0 {
+        return None;
+    }
+
+    let mut sens_args = args.clone();
+    sens_args.point_scaling = PointScaling::Sens;
+    let cap_speed = cap_speed(args);
+
+    let mut weighted_sens = vec![];
+    let mut capped_duration = 0.0;
+    let mut decreasing_duration = 0.0;
+    for sample in samples {
+        let sens = get_point(sample.speed, &sens_args);
+        if !sens.is_finite() {
+            return None;
+        }
+        weighted_sens.push((sens, sample.duration_ms));
+
+        if cap_speed.is_some_and(|cap_speed| sample.speed > cap_speed) {
+            capped_duration += sample.duration_ms;
+        }
+
+        let step = (sample.speed * 1e-3).max(1e-4);
+        let next_speed = sample.speed + step;
+        if next_speed * get_point(next_speed, &sens_args) < sample.speed * sens {
+            decreasing_duration += sample.duration_ms;
+        }
+    }
+
+    let mean_sens = weighted_sens
+        .iter()
+        .map(|(sens, duration)| sens * duration)
+        .sum::<f64>()
+        / total_duration;
+
+    //higher sens means a lower cm/360 so the percentiles swap
+    weighted_sens.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let sens_percentile = |fraction: f64| -> f64 {
+        let mut elapsed = 0.0;
+        for (sens, duration) in &weighted_sens {
+            elapsed += duration;
+            if elapsed >= total_duration * fraction {
+                return *sens;
+            }
+        }
+        return weighted_sens
+            .last()
+            .map(|(sens, _)| *sens)
+            .unwrap_or_default();
+    };
+
+    return Some(UsageMetrics {
+        mean_sens,
+        cm_per_360_low: units::cm_per_360(sens_percentile(0.95), args.dpi, yaw, game_sens),
+        cm_per_360_high: units::cm_per_360(sens_percentile(0.05), args.dpi, yaw, game_sens),
+        capped_fraction: cap_speed.map(|_| capped_duration / total_duration),
+        decreasing_velocity_fraction: decreasing_duration / total_duration,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rawaccel_convert::types::{AccelMode, CapMode, Point};
+
+    fn sample(speed: f64, duration_ms: f64) -> SpeedSample {
+        return SpeedSample { speed, duration_ms };
+    }
+
+    #[test]
+    fn spread_and_capped_time_are_weighted_by_duration() {
+        let args = AccelArgs {
+            mode: AccelMode::Classic,
+            cap_mode: CapMode::Input,
+            cap: Point { x: 15.0, y: 1.5 },
+            ..AccelArgs::default()
+        };
+        //the 1 ms at the slowest speed is under 5% of the recording so it's left out of the spread
+        let samples = [
+            sample(1.0, 1.0),
+            sample(5.0, 30.0),
+            sample(10.0, 20.0),
+            sample(20.0, 50.0),
+        ];
+        let metrics = usage_metrics(&samples, &args, 0.022, 1.0).unwrap();
+
+        let sens = |speed: f64| get_point(speed, &args);
+        let mean_sens =
+            (sens(1.0) + sens(5.0) * 30.0 + sens(10.0) * 20.0 + sens(20.0) * 50.0) / 101.0;
+        assert!((metrics.mean_sens - mean_sens).abs() < 1e-12);
+        assert_eq!(
+            metrics.cm_per_360_low,
+            units::cm_per_360(sens(20.0), args.dpi, 0.022, 1.0)
+        );
+        assert_eq!(
+            metrics.cm_per_360_high,
+            units::cm_per_360(sens(5.0), args.dpi, 0.022, 1.0)
+        );
+        assert_eq!(metrics.capped_fraction, Some(50.0 / 101.0));
+        assert_eq!(metrics.decreasing_velocity_fraction, 0.0);
+    }
+
+    #[test]
+    fn time_where_velocity_drops_is_counted() {
+        //sens steps down to 0.25 at 20 counts/ms, so the output velocity falls from 20 to 5 there
+        let args = AccelArgs {
+            mode: AccelMode::Jump,
+            cap: Point { x: 20.0, y: 0.25 },
+            smooth: 0.0,
+            ..AccelArgs::default()
+        };
+        let samples = [sample(10.0, 40.0), sample(19.99, 20.0), sample(30.0, 40.0)];
+        let metrics = usage_metrics(&samples, &args, 0.022, 1.0).unwrap();
+        assert!((metrics.decreasing_velocity_fraction - 0.2).abs() < 1e-12);
+        assert_eq!(metrics.capped_fraction, None);
+    }
+
+    #[test]
+    fn no_metrics_without_motion() {
+        assert!(usage_metrics(&[], &AccelArgs::default(), 0.022, 1.0).is_none());
+        assert!(usage_metrics(&[sample(5.0, 0.0)], &AccelArgs::default(), 0.022, 1.0).is_none());
+    }
+}
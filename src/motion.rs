@@ -0,0 +1,312 @@
+//gaps longer than this are treated as the mouse being at rest rather than moving very slowly
+const MAX_EVENT_INTERVAL_MS: f64 = 50.0;
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct MotionEvent {
+    pub time_ms: f64,
+    pub dx: f64,
+    pub dy: f64,
+}
+
+#[derive(Clone, Default, PartialEq)]
+pub struct MotionRecording {
+    pub events: Vec<MotionEvent>,
+}
+
+#[derive(Clone, Copy)]
+pub struct SpeedSample {
+    //counts/ms
+    pub speed: f64,
+    pub duration_ms: f64,
+}
+
+pub struct HistogramBin {
+    pub min_speed: f64,
+    pub max_speed: f64,
+    //fraction of the total motion time spent in this bin
+    pub fraction: f64,
+}
+
+//cursor paths in counts starting from the origin, y grows downwards like the screen
+pub struct ReplayPath {
+    pub raw: Vec<[f64; 2]>,
+    pub output: Vec<[f64; 2]>,
+    //sens multiplier applied to each event, output distance over input distance, against time in ms
+    pub sens: Vec<[f64; 2]>,
+}
+
+pub struct MotionStats {
+    pub median_speed: f64,
+    pub p95_speed: f64,
+    //fraction of the total motion time spent faster than the cap speed
+    pub time_above_cap: Option<f64>,
+}
+
+impl MotionRecording {
+    pub fn push(&mut self, time_ms: f64, dx: f64, dy: f64) {
+        //several deltas in the same frame or report share a timestamp
+        if let Some(last) = self.events.last_mut() {
+            if last.time_ms == time_ms {
+                last.dx += dx;
+                last.dy += dy;
+                return;
+            }
+        }
+        self.events.push(MotionEvent { time_ms, dx, dy });
+    }
+
+    pub fn duration_ms(&self) -> f64 {
+        match (self.events.first(), self.events.last()) {
+            (Some(first), Some(last)) => last.time_ms - first.time_ms,
+            _ => 0.0,
+        }
+    }
+
+    pub fn speed_samples(&self) -> Vec<SpeedSample> {
+        let mut samples = vec![];
+        for pair in self.events.windows(2) {
+            if let Some(sample) = speed_sample(&pair[0], &pair[1]) {
+                samples.push(sample);
+            }
+        }
+        return samples;
+    }
+
+    //events between `start_ms` and `end_ms` measured from the start of the recording
+    pub fn slice(&self, start_ms: f64, end_ms: f64) -> MotionRecording {
+        let first_time = match self.events.first() {
+            Some(some) => some.time_ms,
+            None => return MotionRecording::default(),
+        };
+        return MotionRecording {
+            events: self
+                .events
+                .iter()
+                .filter(|event| {
+                    let time = event.time_ms - first_time;
+                    time >= start_ms && time <= end_ms
+                })
+                .copied()
+                .collect(),
+        };
+    }
+
+    //moves a cursor by every delta as is and scaled by `sens` at the speed of that event
+    pub fn replay(&self, sens: impl Fn(f64) -> f64) -> ReplayPath {
+        let mut replay = ReplayPath {
+            raw: vec![[0.0, 0.0]],
+            output: vec![[0.0, 0.0]],
+            sens: vec![],
+        };
+        let first_time = self
+            .events
+            .first()
+            .map(|event| event.time_ms)
+            .unwrap_or_default();
+        let mut raw = [0.0, 0.0];
+        let mut output = [0.0, 0.0];
+        for pair in self.events.windows(2) {
+            let sample = match speed_sample(&pair[0], &pair[1]) {
+                Some(some) => some,
+                None => continue,
+            };
+            let event = &pair[1];
+            let multiplier = sens(sample.speed);
+            raw = [raw[0] + event.dx, raw[1] + event.dy];
+            output = [
+                output[0] + event.dx * multiplier,
+                output[1] + event.dy * multiplier,
+            ];
+            replay.raw.push(raw);
+            replay.output.push(output);
+            replay.sens.push([event.time_ms - first_time, multiplier]);
+        }
+        return replay;
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = "time_ms,dx,dy\n".to_string();
+        for event in &self.events {
+            csv += &format!("{},{},{}\n", event.time_ms, event.dx, event.dy);
+        }
+        return csv;
+    }
+
+    pub fn from_csv(csv: &str) -> Result<MotionRecording, String> {
+        let mut recording = MotionRecording::default();
+        for (i, line) in csv.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("time_ms") {
+                continue;
+            }
+            let values = line
+                .split(',')
+                .map(|value| value.trim().parse::<f64>())
+                .collect::<Result<Vec<f64>, _>>()
+                .map_err(|_| format!("Invalid number on line {}", i + 1))?;
+            match values[..] {
+                [time_ms, dx, dy] => recording.push(time_ms, dx, dy),
+                _ => return Err(format!("Expected time_ms,dx,dy on line {}", i + 1)),
+            }
+        }
+        if recording.events.is_empty() {
+            return Err("Recording contains no motion".to_string());
+        }
+        return Ok(recording);
+    }
+}
+
+//speed of `event` over the time since `previous`
+fn speed_sample(previous: &MotionEvent, event: &MotionEvent) -> Option<SpeedSample> {
+    let interval = (event.time_ms - previous.time_ms).min(MAX_EVENT_INTERVAL_MS);
+    if interval <= 0.0 {
+        return None;
+    }
+    return Some(SpeedSample {
+        speed: event.dx.hypot(event.dy) / interval,
+        duration_ms: interval,
+    });
+}
+
+//time weighted speed percentiles, `None` when there are no samples
+pub fn motion_stats(samples: &[SpeedSample], cap_speed: Option<f64>) -> Option<MotionStats> {
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(|a, b| a.speed.total_cmp(&b.speed));
+    let total_duration: f64 = sorted.iter().map(|sample| sample.duration_ms).sum();
+    if total_duration <= 0.0 {
+        return None;
+    }
+
+    let percentile = |fraction: f64| -> f64 {
+        let mut elapsed = 0.0;
+        for sample in &sorted {
+            elapsed += sample.duration_ms;
+            if elapsed >= total_duration * fraction {
+                return sample.speed;
+            }
+        }
+        return sorted.last().map(|sample| sample.speed).unwrap_or_default();
+    };
+
+    let time_above_cap = cap_speed.map(|cap_speed| {
+        let duration_above: f64 = sorted
+            .iter()
+            .filter(|sample| sample.speed > cap_speed)
+            .map(|sample| sample.duration_ms)
+            .sum();
+        duration_above / total_duration
+    });
+
+    return Some(MotionStats {
+        median_speed: percentile(0.5),
+        p95_speed: percentile(0.95),
+        time_above_cap,
+    });
+}
+
+//time weighted distribution of speeds between zero and `max_speed`, faster samples go in the last bin
+pub fn speed_histogram(
+    samples: &[SpeedSample],
+    max_speed: f64,
+    bin_count: usize,
+) -> Vec<HistogramBin> {
+    let bin_width = max_speed / bin_count as f64;
+    let mut durations = vec![0.0; bin_count];
+    let mut total_duration = 0.0;
+    for sample in samples {
+        let bin = ((sample.speed / bin_width) as usize).min(bin_count - 1);
+        durations[bin] += sample.duration_ms;
+        total_duration += sample.duration_ms;
+    }
+
+    return durations
+        .iter()
+        .enumerate()
+        .map(|(i, duration)| HistogramBin {
+            min_speed: i as f64 * bin_width,
+            max_speed: (i + 1) as f64 * bin_width,
+            fraction: match total_duration > 0.0 {
+                true => duration / total_duration,
+                false => 0.0,
+            },
+        })
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recording(events: &[(f64, f64, f64)]) -> MotionRecording {
+        let mut recording = MotionRecording::default();
+        for (time_ms, dx, dy) in events {
+            recording.push(*time_ms, *dx, *dy);
+        }
+        return recording;
+    }
+
+    #[test]
+    fn csv_round_trip() {
+        let recording = recording(&[(0.0, 1.0, -2.0), (1.5, 3.25, 0.0), (2.0, -4.0, 5.0)]);
+        let csv = recording.to_csv();
+        assert!(csv.starts_with("time_ms,dx,dy\n"));
+        assert!(MotionRecording::from_csv(&csv).unwrap() == recording);
+    }
+
+    #[test]
+    fn csv_errors() {
+        assert!(MotionRecording::from_csv("time_ms,dx,dy\n").is_err());
+        assert!(MotionRecording::from_csv("0,1\n").is_err());
+        assert!(MotionRecording::from_csv("0,1,x\n").is_err());
+    }
+
+    #[test]
+    fn same_timestamp_deltas_merge() {
+        let recording = recording(&[(0.0, 1.0, 1.0), (1.0, 2.0, 0.0), (1.0, 1.0, 4.0)]);
+        assert_eq!(recording.events.len(), 2);
+        assert_eq!((recording.events[1].dx, recording.events[1].dy), (3.0, 4.0));
+    }
+
+    #[test]
+    fn speed_samples_cap_long_gaps() {
+        let recording = recording(&[(0.0, 0.0, 0.0), (2.0, 3.0, 4.0), (1002.0, 10.0, 0.0)]);
+        let samples = recording.speed_samples();
+        assert_eq!(samples.len(), 2);
+        assert_eq!((samples[0].speed, samples[0].duration_ms), (2.5, 2.0));
+        assert_eq!(
+            (samples[1].speed, samples[1].duration_ms),
+            (10.0 / MAX_EVENT_INTERVAL_MS, MAX_EVENT_INTERVAL_MS)
+        );
+    }
+
+    #[test]
+    fn speed_histogram_is_time_weighted() {
+        let samples = [
+            SpeedSample {
+                speed: 1.0,
+                duration_ms: 3.0,
+            },
+            SpeedSample {
+                speed: 6.0,
+                duration_ms: 1.0,
+            },
+            //past the last bin
+            SpeedSample {
+                speed: 50.0,
+                duration_ms: 4.0,
+            },
+        ];
+        let bins = speed_histogram(&samples, 10.0, 2);
+        assert_eq!(bins.len(), 2);
+        assert_eq!((bins[0].min_speed, bins[0].max_speed), (0.0, 5.0));
+        assert_eq!((bins[1].min_speed, bins[1].max_speed), (5.0, 10.0));
+        assert_eq!(bins[0].fraction, 3.0 / 8.0);
+        assert_eq!(bins[1].fraction, 5.0 / 8.0);
+    }
+
+    #[test]
+    fn empty_speed_histogram() {
+        let bins = speed_histogram(&[], 10.0, 4);
+        assert!(bins.iter().all(|bin| bin.fraction == 0.0));
+    }
+}
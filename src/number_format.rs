@@ -0,0 +1,173 @@
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum DecimalSeparator {
+    Point,
+    Comma,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum FieldSeparator {
+    Comma,
+    Semicolon,
+    Tab,
+    Space,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum Precision {
+    //shortest representation that reads back to the same value
+    Full,
+    Decimals(usize),
+    SignificantDigits(usize),
+}
+
+//how exported points are written out
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct NumberFormat {
+    pub decimal_separator: DecimalSeparator,
+    pub field_separator: FieldSeparator,
+    pub precision: Precision,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            decimal_separator: DecimalSeparator::Point,
+            field_separator: FieldSeparator::Comma,
+            precision: Precision::Full,
+        }
+    }
+}
+
+impl DecimalSeparator {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DecimalSeparator::Point => "Point (0.5)",
+            DecimalSeparator::Comma => "Comma (0,5)",
+        }
+    }
+
+    pub fn char(&self) -> char {
+        match self {
+            DecimalSeparator::Point => '.',
+            DecimalSeparator::Comma => ',',
+        }
+    }
+}
+
+impl FieldSeparator {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FieldSeparator::Comma => "Comma",
+            FieldSeparator::Semicolon => "Semicolon",
+            FieldSeparator::Tab => "Tab",
+            FieldSeparator::Space => "Space",
+        }
+    }
+
+    pub fn char(&self) -> char {
+        match self {
+            FieldSeparator::Comma => ',',
+            FieldSeparator::Semicolon => ';',
+            FieldSeparator::Tab => '\t',
+            FieldSeparator::Space => ' ',
+        }
+    }
+}
+
+impl NumberFormat {
+    pub fn number(&self, value: f64) -> String {
+        let number = match self.precision {
+            Precision::Full => value.to_string(),
+            Precision::Decimals(decimals) => format!("{:.*}", decimals, value),
+            Precision::SignificantDigits(digits) => significant_digits(value, digits),
+        };
+        return match self.decimal_separator {
+            DecimalSeparator::Point => number,
+            DecimalSeparator::Comma => number.replace('.', ","),
+        };
+    }
+
+    //one row of several values
+    pub fn fields(&self, values: &[f64]) -> String {
+        return values
+            .iter()
+            .map(|value| self.number(*value))
+            .collect::<Vec<String>>()
+            .join(&self.field_separator.char().to_string());
+    }
+
+    //a decimal comma in comma separated fields can't be read back
+    pub fn is_ambiguous(&self) -> bool {
+        return self.decimal_separator.char() == self.field_separator.char();
+    }
+
+    //why rows of fields written in this format can't be read back, `row_end` is what closes each row
+    pub fn export_problem(&self, row_end: Option<char>) -> Option<String> {
+        if self.is_ambiguous() {
+            return Some("Decimal and field separators are the same".to_string());
+        }
+        if row_end == Some(self.field_separator.char()) {
+            return Some(format!(
+                "{} field separators can't be told apart from the end of each row",
+                self.field_separator.label()
+            ));
+        }
+        return None;
+    }
+}
+
+fn significant_digits(value: f64, digits: usize) -> String {
+    if value == 0.0 || !value.is_finite() {
+        return value.to_string();
+    }
+    let magnitude = value.abs().log10().floor() as i32;
+    let decimals = digits.max(1) as i32 - 1 - magnitude;
+    if decimals >= 0 {
+        return format!("{:.*}", decimals as usize, value);
+    }
+    let rounding = 10f64.powi(-decimals);
+    return format!("{:.0}", (value / rounding).round() * rounding);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn separators_and_precision() {
+        let number_format = NumberFormat {
+            decimal_separator: DecimalSeparator::Comma,
+            field_separator: FieldSeparator::Semicolon,
+            precision: Precision::Decimals(2),
+        };
+        assert_eq!(number_format.fields(&[1.5, 0.125]), "1,50;0,12");
+        assert_eq!(NumberFormat::default().fields(&[1.5, 0.125]), "1.5,0.125");
+    }
+
+    #[test]
+    fn export_problems() {
+        let decimal_comma = NumberFormat {
+            decimal_separator: DecimalSeparator::Comma,
+            ..NumberFormat::default()
+        };
+        assert!(decimal_comma.is_ambiguous());
+        assert!(decimal_comma.export_problem(None).is_some());
+
+        let semicolon = NumberFormat {
+            field_separator: FieldSeparator::Semicolon,
+            ..NumberFormat::default()
+        };
+        assert_eq!(semicolon.export_problem(None), None);
+        //look up table rows end in `;`
+        assert!(semicolon.export_problem(Some(';')).is_some());
+        assert_eq!(NumberFormat::default().export_problem(Some(';')), None);
+    }
+
+    #[test]
+    fn significant_digit_rounding() {
+        assert_eq!(significant_digits(0.0012345, 3), "0.00123");
+        assert_eq!(significant_digits(12345.0, 2), "12000");
+        assert_eq!(significant_digits(0.0, 3), "0");
+    }
+}
@@ -0,0 +1,629 @@
+use std::ops::{Bound, RangeBounds};
+
+use rawaccel_convert::types::{AccelArgs, AccelMode, CapMode};
+
+use crate::gui::{cap_speed, RawaccelConvertSettings, SETTINGS_VERSION};
+
+const ALL_MODES: &[AccelMode] = &[
+    AccelMode::Linear,
+    AccelMode::Classic,
+    AccelMode::Jump,
+    AccelMode::Natural,
+    AccelMode::Synchronous,
+    AccelMode::Power,
+    AccelMode::Motivity,
+    AccelMode::Lookup,
+    AccelMode::Noaccel,
+];
+const ALL_CAP_MODES: &[CapMode] = &[CapMode::Input, CapMode::Output, CapMode::InputOutput];
+
+//a numeric curve parameter edited through a settings string
+//dpi and the export point count are whole numbers with their own fields and the look up table is text,
+//none of them are curve parameters the sliders, solver, fits or handles could work on so they stay out
+pub struct Parameter {
+    //also the id of its sidebar field, see `validation::field_id`
+    pub id: &'static str,
+    pub label: &'static str,
+    pub help: &'static str,
+    pub settings_string: fn(&mut RawaccelConvertSettings) -> &mut String,
+    //where settings saved by older versions kept the value, see `migrate_legacy`
+    pub legacy_settings_string: Option<fn(&mut RawaccelConvertSettings) -> &mut String>,
+    pub default: &'static str,
+    pub get: fn(&AccelArgs) -> f64,
+    pub set: fn(&mut AccelArgs, f64),
+    //values outside this are rejected by validation
+    pub valid: (Bound<f64>, Bound<f64>),
+    //range of the sidebar slider, lower end is above 0 for logarithmic parameters
+    pub slider_range: fn(&AccelArgs) -> (f64, f64),
+    //range searched by the solver and curve fit
+    pub min: f64,
+    pub max: f64,
+    //searched in log space, only used for strictly positive ranges
+    pub logarithmic: bool,
+    pub free_by_default: bool,
+    pub dpi_scaling: DpiScaling,
+    pub modes: &'static [AccelMode],
+    //only checked for modes with a cap type
+    pub cap_modes: &'static [CapMode],
+    //shown above the cap type dropdown instead of below it
+    pub before_cap_type: bool,
+}
+
+//how a parameter changes when the same physical hand speed produces `new / old` times the counts
+#[derive(Clone, Copy, PartialEq)]
+pub enum DpiScaling {
+    Unchanged,
+    //input speeds in counts/ms
+    Speed,
+    //rates applied to input speed and the sens multiplier
+    InverseSpeed,
+}
+
+impl Parameter {
+    pub fn applies_to(&self, args: &AccelArgs) -> bool {
+        return self.modes.contains(&args.mode) && self.cap_modes.contains(&args.cap_mode);
+    }
+
+    pub fn is_valid(&self, value: f64) -> bool {
+        return value.is_finite() && self.valid.contains(&value);
+    }
+
+    //finishes a sentence starting with the label
+    pub fn valid_description(&self) -> String {
+        match self.valid {
+            (Bound::Excluded(min), Bound::Unbounded) => format!("must be above {}", min),
+            (Bound::Included(0.0), Bound::Unbounded) => "can't be negative".to_string(),
+            (Bound::Included(min), Bound::Unbounded) => format!("must be at least {}", min),
+            (Bound::Included(min), Bound::Included(max)) => {
+                format!("must be between {} and {}", min, max)
+            }
+            _ => "is out of range".to_string(),
+        }
+    }
+}
+
+pub const SENS_MULTIPLIER: Parameter = Parameter {
+    id: "sens_multiplier",
+    label: "Sens Multiplier",
+    help: "Multiplies the sensitivity at every speed",
+    settings_string: |settings| &mut settings.sens_multiplier_string,
+    legacy_settings_string: None,
+    default: "1.0",
+    get: |args| args.sens_multiplier,
+    set: |args, value| args.sens_multiplier = value,
+    valid: (Bound::Excluded(0.0), Bound::Unbounded),
+    slider_range: |_| (0.05, 10.0),
+    min: 0.001,
+    max: 100.0,
+    logarithmic: true,
+    free_by_default: true,
+    dpi_scaling: DpiScaling::InverseSpeed,
+    modes: ALL_MODES,
+    cap_modes: ALL_CAP_MODES,
+    before_cap_type: false,
+};
+
+const ACCELERATION: Parameter = Parameter {
+    id: "acceleration",
+    label: "Acceleration",
+    help: "How quickly sensitivity rises with speed",
+    settings_string: |settings| &mut settings.acceleration_string,
+    legacy_settings_string: None,
+    default: "0.005",
+    get: |args| args.acceleration,
+    set: |args, value| args.acceleration = value,
+    valid: (Bound::Included(0.0), Bound::Unbounded),
+    slider_range: |args| match args.mode {
+        AccelMode::Classic => (0.00001, 0.5),
+        _ => (0.0001, 1.0),
+    },
+    min: 0.000001,
+    max: 10.0,
+    logarithmic: true,
+    free_by_default: true,
+    dpi_scaling: DpiScaling::InverseSpeed,
+    modes: &[AccelMode::Linear, AccelMode::Classic],
+    cap_modes: &[CapMode::Input, CapMode::Output],
+    before_cap_type: true,
+};
+
+const SCALE: Parameter = Parameter {
+    id: "scale",
+    label: "Scale",
+    help: "Multiplies input speed before the exponent is applied",
+    settings_string: |settings| &mut settings.scale_string,
+    legacy_settings_string: None,
+    default: "1",
+    get: |args| args.scale,
+    set: |args, value| args.scale = value,
+    valid: (Bound::Excluded(0.0), Bound::Unbounded),
+    slider_range: |_| (0.001, 10.0),
+    min: 0.000001,
+    max: 100.0,
+    logarithmic: true,
+    free_by_default: true,
+    dpi_scaling: DpiScaling::InverseSpeed,
+    modes: &[AccelMode::Power],
+    cap_modes: &[CapMode::Input, CapMode::Output],
+    before_cap_type: true,
+};
+
+const CAP_INPUT: Parameter = Parameter {
+    id: "cap_input",
+    label: "Cap: Input",
+    help: "Input speed in counts/ms where the curve stops rising",
+    settings_string: |settings| &mut settings.cap_input_string,
+    legacy_settings_string: None,
+    default: "15",
+    get: |args| args.cap.x,
+    set: |args, value| args.cap.x = value,
+    valid: (Bound::Included(0.0), Bound::Unbounded),
+    slider_range: |args| (0.1, plotted_speed(args)),
+    min: 0.1,
+    max: 1000.0,
+    logarithmic: true,
+    free_by_default: true,
+    dpi_scaling: DpiScaling::Speed,
+    modes: &[AccelMode::Linear, AccelMode::Classic, AccelMode::Power],
+    cap_modes: &[CapMode::Input, CapMode::InputOutput],
+    before_cap_type: false,
+};
+
+const CAP_OUTPUT: Parameter = Parameter {
+    id: "cap_output",
+    label: "Cap: Output",
+    help: "Highest sensitivity, or gain when gain is enabled",
+    settings_string: |settings| &mut settings.cap_output_string,
+    legacy_settings_string: None,
+    default: "1.5",
+    get: |args| args.cap.y,
+    set: |args, value| args.cap.y = value,
+    valid: (Bound::Excluded(0.0), Bound::Unbounded),
+    slider_range: |args| match args.gain {
+        true => (1.0, 10.0),
+        false => (0.1, 10.0),
+    },
+    min: 0.01,
+    max: 100.0,
+    logarithmic: true,
+    free_by_default: true,
+    dpi_scaling: DpiScaling::Unchanged,
+    modes: &[AccelMode::Linear, AccelMode::Classic, AccelMode::Power],
+    cap_modes: &[CapMode::Output, CapMode::InputOutput],
+    before_cap_type: false,
+};
+
+const DECAY_RATE: Parameter = Parameter {
+    id: "decay",
+    label: "Decay Rate",
+    help: "How quickly sensitivity approaches the limit",
+    settings_string: |settings| &mut settings.decay_string,
+    legacy_settings_string: None,
+    default: "0.1",
+    get: |args| args.decay_rate,
+    set: |args, value| args.decay_rate = value,
+    valid: (Bound::Excluded(0.0), Bound::Unbounded),
+    slider_range: |_| (0.001, 1.0),
+    min: 0.000001,
+    max: 10.0,
+    logarithmic: true,
+    free_by_default: true,
+    dpi_scaling: DpiScaling::InverseSpeed,
+    modes: &[AccelMode::Natural],
+    cap_modes: ALL_CAP_MODES,
+    before_cap_type: false,
+};
+
+const INPUT_OFFSET: Parameter = Parameter {
+    id: "input_offset",
+    label: "Input Offset",
+    help: "Input speed in counts/ms below which sensitivity stays at the base",
+    settings_string: |settings| &mut settings.input_offset_string,
+    legacy_settings_string: None,
+    default: "0",
+    get: |args| args.input_offset,
+    set: |args, value| args.input_offset = value,
+    valid: (Bound::Included(0.0), Bound::Unbounded),
+    slider_range: |args| (0.0, plotted_speed(args)),
+    min: 0.0,
+    max: 1000.0,
+    logarithmic: false,
+    free_by_default: false,
+    dpi_scaling: DpiScaling::Speed,
+    modes: &[AccelMode::Linear, AccelMode::Classic, AccelMode::Natural],
+    cap_modes: ALL_CAP_MODES,
+    before_cap_type: false,
+};
+
+const LIMIT: Parameter = Parameter {
+    id: "limit",
+    label: "Limit",
+    help: "Sensitivity the curve approaches at high speed",
+    settings_string: |settings| &mut settings.limit_string,
+    legacy_settings_string: None,
+    default: "1.5",
+    get: |args| args.limit,
+    set: |args, value| args.limit = value,
+    valid: (Bound::Excluded(0.0), Bound::Unbounded),
+    slider_range: |_| (0.1, 10.0),
+    min: 0.01,
+    max: 100.0,
+    logarithmic: true,
+    free_by_default: true,
+    dpi_scaling: DpiScaling::Unchanged,
+    modes: &[AccelMode::Natural],
+    cap_modes: ALL_CAP_MODES,
+    before_cap_type: false,
+};
+
+const POWER_CLASSIC: Parameter = Parameter {
+    id: "exponent_classic",
+    label: "Power",
+    help: "Exponent of the classic curve, 2 is linear",
+    settings_string: |settings| &mut settings.exponent_classic_string,
+    legacy_settings_string: None,
+    default: "2",
+    get: |args| args.exponent_classic,
+    set: |args, value| args.exponent_classic = value,
+    valid: (Bound::Excluded(1.0), Bound::Unbounded),
+    slider_range: |_| (1.01, 6.0),
+    min: 1.0,
+    max: 10.0,
+    logarithmic: false,
+    free_by_default: true,
+    dpi_scaling: DpiScaling::Unchanged,
+    modes: &[AccelMode::Classic],
+    cap_modes: ALL_CAP_MODES,
+    before_cap_type: false,
+};
+
+const EXPONENT_POWER: Parameter = Parameter {
+    id: "exponent_power",
+    label: "Exponent",
+    help: "Exponent applied to the scaled input speed",
+    settings_string: |settings| &mut settings.exponent_power_string,
+    legacy_settings_string: None,
+    default: "0.05",
+    get: |args| args.exponent_power,
+    set: |args, value| args.exponent_power = value,
+    valid: (Bound::Excluded(0.0), Bound::Unbounded),
+    slider_range: |_| (0.01, 2.0),
+    min: 0.001,
+    max: 10.0,
+    logarithmic: true,
+    free_by_default: true,
+    dpi_scaling: DpiScaling::Unchanged,
+    modes: &[AccelMode::Power],
+    cap_modes: ALL_CAP_MODES,
+    before_cap_type: false,
+};
+
+const OUTPUT_OFFSET: Parameter = Parameter {
+    id: "output_offset",
+    label: "Output Offset",
+    help: "Sensitivity added before the power curve starts",
+    settings_string: |settings| &mut settings.output_offset_string,
+    legacy_settings_string: None,
+    default: "0",
+    get: |args| args.output_offset,
+    set: |args, value| args.output_offset = value,
+    valid: (Bound::Included(0.0), Bound::Unbounded),
+    slider_range: |_| (0.0, 5.0),
+    min: 0.0,
+    max: 10.0,
+    logarithmic: false,
+    free_by_default: false,
+    dpi_scaling: DpiScaling::Unchanged,
+    modes: &[AccelMode::Power],
+    cap_modes: ALL_CAP_MODES,
+    before_cap_type: false,
+};
+
+const GAMMA: Parameter = Parameter {
+    id: "gamma",
+    label: "Gamma",
+    help: "How quickly sensitivity moves between the low and high ends",
+    settings_string: |settings| &mut settings.gamma_string,
+    legacy_settings_string: None,
+    default: "1",
+    get: |args| args.gamma,
+    set: |args, value| args.gamma = value,
+    valid: (Bound::Excluded(0.0), Bound::Unbounded),
+    slider_range: |_| (0.01, 10.0),
+    min: 0.001,
+    max: 100.0,
+    logarithmic: true,
+    free_by_default: true,
+    dpi_scaling: DpiScaling::Unchanged,
+    modes: &[AccelMode::Synchronous],
+    cap_modes: ALL_CAP_MODES,
+    before_cap_type: false,
+};
+
+const GROWTH_RATE: Parameter = Parameter {
+    id: "growth_rate",
+    label: "Growth Rate",
+    help: "How quickly sensitivity moves between the low and high ends",
+    settings_string: |settings| &mut settings.growth_rate_string,
+    legacy_settings_string: Some(|settings| &mut settings.gamma_string),
+    modes: &[AccelMode::Motivity],
+    ..GAMMA
+};
+
+const SMOOTH: Parameter = Parameter {
+    id: "smooth",
+    label: "Smooth",
+    help: "How gradual the transition is, 0 is an instant step",
+    settings_string: |settings| &mut settings.smooth_string,
+    legacy_settings_string: None,
+    default: "0.5",
+    get: |args| args.smooth,
+    set: |args, value| args.smooth = value,
+    valid: (Bound::Included(0.0), Bound::Included(1.0)),
+    slider_range: |_| (0.0, 1.0),
+    min: 0.0,
+    max: 1.0,
+    logarithmic: false,
+    free_by_default: true,
+    dpi_scaling: DpiScaling::Unchanged,
+    modes: &[AccelMode::Jump, AccelMode::Synchronous],
+    cap_modes: ALL_CAP_MODES,
+    before_cap_type: false,
+};
+
+const JUMP_INPUT: Parameter = Parameter {
+    id: "input",
+    label: "Input",
+    help: "Input speed in counts/ms where the jump happens",
+    settings_string: |settings| &mut settings.input_string,
+    legacy_settings_string: None,
+    default: "15",
+    get: |args| args.cap.x,
+    set: |args, value| args.cap.x = value,
+    valid: (Bound::Excluded(0.0), Bound::Unbounded),
+    slider_range: |args| (0.1, plotted_speed(args)),
+    min: 0.1,
+    max: 1000.0,
+    logarithmic: true,
+    free_by_default: true,
+    dpi_scaling: DpiScaling::Speed,
+    modes: &[AccelMode::Jump],
+    cap_modes: ALL_CAP_MODES,
+    before_cap_type: false,
+};
+
+const JUMP_OUTPUT: Parameter = Parameter {
+    id: "output",
+    label: "Output",
+    help: "Sensitivity after the jump",
+    settings_string: |settings| &mut settings.output_string,
+    legacy_settings_string: None,
+    default: "1.5",
+    get: |args| args.cap.y,
+    set: |args, value| args.cap.y = value,
+    valid: (Bound::Excluded(0.0), Bound::Unbounded),
+    slider_range: |_| (0.1, 10.0),
+    min: 0.01,
+    max: 100.0,
+    logarithmic: true,
+    free_by_default: true,
+    dpi_scaling: DpiScaling::Unchanged,
+    modes: &[AccelMode::Jump],
+    cap_modes: ALL_CAP_MODES,
+    before_cap_type: false,
+};
+
+const MOTIVITY: Parameter = Parameter {
+    id: "motivity",
+    label: "Motivity",
+    help: "Ratio between the highest and lowest sensitivity",
+    settings_string: |settings| &mut settings.motivity_string,
+    legacy_settings_string: None,
+    default: "1.5",
+    get: |args| args.motivity,
+    set: |args, value| args.motivity = value,
+    valid: (Bound::Excluded(1.0), Bound::Unbounded),
+    slider_range: |_| (1.01, 10.0),
+    min: 1.001,
+    max: 100.0,
+    logarithmic: true,
+    free_by_default: true,
+    dpi_scaling: DpiScaling::Unchanged,
+    modes: &[AccelMode::Synchronous, AccelMode::Motivity],
+    cap_modes: ALL_CAP_MODES,
+    before_cap_type: false,
+};
+
+const SYNC_SPEED: Parameter = Parameter {
+    id: "syncspeed",
+    label: "SyncSpeed",
+    help: "Input speed in counts/ms where sensitivity is 1",
+    settings_string: |settings| &mut settings.syncspeed_string,
+    legacy_settings_string: None,
+    default: "5",
+    get: |args| args.sync_speed,
+    set: |args, value| args.sync_speed = value,
+    valid: (Bound::Excluded(0.0), Bound::Unbounded),
+    slider_range: |args| (0.1, plotted_speed(args)),
+    min: 0.1,
+    max: 1000.0,
+    logarithmic: true,
+    free_by_default: true,
+    dpi_scaling: DpiScaling::Speed,
+    modes: &[AccelMode::Synchronous],
+    cap_modes: ALL_CAP_MODES,
+    before_cap_type: false,
+};
+
+const MIDPOINT: Parameter = Parameter {
+    id: "midpoint",
+    label: "Midpoint",
+    help: "Input speed in counts/ms halfway through the transition",
+    settings_string: |settings| &mut settings.midpoint_string,
+    legacy_settings_string: Some(|settings| &mut settings.syncspeed_string),
+    modes: &[AccelMode::Motivity],
+    ..SYNC_SPEED
+};
+
+//in sidebar order, every parameter of a mode comes out in the order it is shown
+const MODE_PARAMETERS: [&Parameter; 18] = [
+    &ACCELERATION,
+    &SCALE,
+    &CAP_INPUT,
+    &CAP_OUTPUT,
+    &DECAY_RATE,
+    &INPUT_OFFSET,
+    &LIMIT,
+    &POWER_CLASSIC,
+    &EXPONENT_POWER,
+    &OUTPUT_OFFSET,
+    &GAMMA,
+    &GROWTH_RATE,
+    &SMOOTH,
+    &JUMP_INPUT,
+    &JUMP_OUTPUT,
+    &MOTIVITY,
+    &SYNC_SPEED,
+    &MIDPOINT,
+];
+
+//every settings string holding a curve parameter, each listed once
+pub fn all_parameters() -> Vec<&'static Parameter> {
+    return [vec![&SENS_MULTIPLIER], MODE_PARAMETERS.to_vec()].concat();
+}
+
+//the parameters shown in the sidebar for the mode and cap type of `args`, without the sens multiplier
+pub fn mode_parameters(args: &AccelArgs) -> Vec<&'static Parameter> {
+    return MODE_PARAMETERS
+        .iter()
+        .filter(|parameter| parameter.applies_to(args))
+        .copied()
+        .collect();
+}
+
+//a point on the curve that can be dragged on the plot to change the parameters it stands for
+pub struct Handle {
+    pub label: &'static str,
+    //input speed in counts/ms, the handle sits on the curve at this speed
+    pub speed: f64,
+    //set to the speed the handle is dragged to
+    pub speed_parameter: Option<&'static Parameter>,
+    //scaled by how far the handle is dragged up or down relative to the curve
+    pub scale_parameter: Option<&'static Parameter>,
+}
+
+//handles for the parameters of `args` that sit on a visible point of the curve
+//power's output offset gets none, it's only reached at speed 0 where the log axes can't place a handle
+//and the velocity view pins every curve to the origin, and a drag relative to its default of 0 can't move it
+pub fn handles(args: &AccelArgs) -> Vec<Handle> {
+    let mut handles = vec![];
+    if has_cap(&args.mode) {
+        let cap = match args.cap_mode {
+            CapMode::Input => (args.cap.x > 0.0).then_some((Some(&CAP_INPUT), None)),
+            CapMode::Output => Some((None, Some(&CAP_OUTPUT))),
+            CapMode::InputOutput => {
+                (args.cap.x > 0.0).then_some((Some(&CAP_INPUT), Some(&CAP_OUTPUT)))
+            }
+        };
+        if let (Some((speed_parameter, scale_parameter)), Some(speed)) = (cap, cap_speed(args)) {
+            handles.push(Handle {
+                label: "Cap",
+                speed,
+                speed_parameter,
+                scale_parameter,
+            });
+        }
+    }
+    if INPUT_OFFSET.applies_to(args) {
+        handles.push(Handle {
+            label: INPUT_OFFSET.label,
+            speed: args.input_offset,
+            speed_parameter: Some(&INPUT_OFFSET),
+            scale_parameter: None,
+        });
+    }
+    let speed_handle = match args.mode {
+        AccelMode::Jump => Some(("Jump", &JUMP_INPUT, Some(&JUMP_OUTPUT))),
+        AccelMode::Synchronous => Some((SYNC_SPEED.label, &SYNC_SPEED, None)),
+        AccelMode::Motivity => Some((MIDPOINT.label, &MIDPOINT, None)),
+        _ => None,
+    };
+    if let Some((label, speed_parameter, scale_parameter)) = speed_handle {
+        handles.push(Handle {
+            label,
+            speed: (speed_parameter.get)(args),
+            speed_parameter: Some(speed_parameter),
+            scale_parameter,
+        });
+    }
+    return handles;
+}
+
+//highest input speed on the plot, in counts/ms
+fn plotted_speed(args: &AccelArgs) -> f64 {
+    return (args.dpi / 20).max(1) as f64;
+}
+
+//modes where a cap type is picked
+pub fn has_cap(mode: &AccelMode) -> bool {
+    return matches!(
+        mode,
+        AccelMode::Linear | AccelMode::Classic | AccelMode::Power
+    );
+}
+
+pub fn set_defaults(settings: &mut RawaccelConvertSettings) {
+    for parameter in all_parameters() {
+        *(parameter.settings_string)(settings) = parameter.default.to_string();
+    }
+}
+
+//settings saved before a parameter had its own field keep its value where the older version stored it,
+//fields missing for any other reason already get their default from `RawaccelConvertSettings::default`
+pub fn migrate_legacy(settings: &mut RawaccelConvertSettings) {
+    if settings.settings_version >= SETTINGS_VERSION {
+        return;
+    }
+    for parameter in all_parameters() {
+        if let Some(legacy_settings_string) = parameter.legacy_settings_string {
+            *(parameter.settings_string)(settings) = legacy_settings_string(settings).clone();
+        }
+    }
+    settings.settings_version = SETTINGS_VERSION;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_values_are_migrated_once() {
+        let mut settings = RawaccelConvertSettings {
+            settings_version: 0,
+            gamma_string: "2.5".to_string(),
+            syncspeed_string: "8".to_string(),
+            ..RawaccelConvertSettings::default()
+        };
+        migrate_legacy(&mut settings);
+        assert_eq!(settings.growth_rate_string, "2.5");
+        assert_eq!(settings.midpoint_string, "8");
+        assert_eq!(settings.settings_version, SETTINGS_VERSION);
+
+        //a field cleared after migrating stays cleared
+        settings.growth_rate_string.clear();
+        migrate_legacy(&mut settings);
+        assert_eq!(settings.growth_rate_string, "");
+    }
+
+    #[test]
+    fn current_settings_are_left_alone() {
+        let mut settings = RawaccelConvertSettings {
+            gamma_string: "2.5".to_string(),
+            midpoint_string: String::default(),
+            ..RawaccelConvertSettings::default()
+        };
+        migrate_legacy(&mut settings);
+        assert_eq!(settings.growth_rate_string, GROWTH_RATE.default);
+        assert_eq!(settings.midpoint_string, "");
+    }
+}
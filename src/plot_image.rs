@@ -0,0 +1,865 @@
+use egui::epaint::{self, Color32, Pos2, Rect};
+
+use crate::units::PlotTransform;
+
+//size of the image in points, png exports scale it up to the chosen resolution
+const WIDTH: f32 = 800.0;
+const MARGIN_LEFT: f32 = 70.0;
+const MARGIN_RIGHT: f32 = 20.0;
+const MARGIN_TOP: f32 = 30.0;
+const MARGIN_BOTTOM: f32 = 50.0;
+//room for the tick labels of the top and right axes
+const SECONDARY_AXIS_MARGIN: f32 = 50.0;
+const DASH_LENGTH: f32 = 8.0;
+const DASH_GAP: f32 = 6.0;
+const MARKER_RADIUS: f32 = 3.0;
+const FONT_SIZE: f32 = 13.0;
+//a larger canvas takes seconds to draw and can exceed the browser's memory
+pub const MAX_PNG_SIZE: u32 = 4096;
+const TARGET_TICKS: f64 = 8.0;
+
+const BACKGROUND: Color32 = Color32::WHITE;
+const TEXT: Color32 = Color32::from_rgb(30, 30, 30);
+const GRID: Color32 = Color32::from_rgb(225, 225, 225);
+const AXIS: Color32 = Color32::from_rgb(90, 90, 90);
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum LineStyle {
+    Solid,
+    Dashed,
+    //unconnected points
+    Markers,
+}
+
+//a curve or set of points, the central plot draws these too so exported images look the same
+pub struct PlotLine {
+    pub name: String,
+    pub color: Color32,
+    pub style: LineStyle,
+    //in plot coordinates, the same ones the central plot uses
+    pub points: Vec<[f64; 2]>,
+}
+
+//a filled rectangle like a histogram bar or shaded speed range, in plot coordinates
+pub struct PlotArea {
+    pub name: String,
+    pub fill: Color32,
+    pub stroke: Color32,
+    pub min: [f64; 2],
+    pub max: [f64; 2],
+}
+
+//one plot, stacked views export one pane each
+pub struct ImagePane {
+    pub transform: PlotTransform,
+    pub bounds: ([f64; 2], [f64; 2]),
+    pub x_label: String,
+    pub y_label: String,
+    //the second speed unit on top and cm/360 or sens on the right like the central plot
+    pub secondary_axes: bool,
+    pub areas: Vec<PlotArea>,
+    pub lines: Vec<PlotLine>,
+}
+
+//everything on the central plot that ends up in an exported image
+pub struct PlotImage {
+    pub panes: Vec<ImagePane>,
+}
+
+//drawing steps in points shared by the svg and png output
+enum Primitive {
+    Polyline {
+        points: Vec<Pos2>,
+        color: Color32,
+        width: f32,
+    },
+    Text {
+        pos: Pos2,
+        anchor: egui::Align2,
+        text: String,
+        color: Color32,
+    },
+    Rect {
+        rect: Rect,
+        fill: Color32,
+        stroke: Color32,
+    },
+    Circle {
+        center: Pos2,
+        radius: f32,
+        color: Color32,
+    },
+}
+
+pub fn to_svg(image: &PlotImage, aspect_ratio: f32) -> String {
+    let size = egui::vec2(WIDTH, WIDTH / aspect_ratio);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" font-family=\"sans-serif\" font-size=\"{}\">\n",
+        size.x, size.y, size.x, size.y, FONT_SIZE
+    );
+    svg += &format!(
+        "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n",
+        svg_color(BACKGROUND)
+    );
+    for primitive in layout(image, size) {
+        match primitive {
+            Primitive::Polyline {
+                points,
+                color,
+                width,
+            } => {
+                let points = points
+                    .iter()
+                    .map(|point| format!("{:.2},{:.2}", point.x, point.y))
+                    .collect::<Vec<String>>()
+                    .join(" ");
+                svg += &format!(
+                    "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-opacity=\"{}\" stroke-width=\"{}\" stroke-linejoin=\"round\"/>\n",
+                    points,
+                    svg_color(color),
+                    svg_opacity(color),
+                    width
+                );
+            }
+            Primitive::Text {
+                pos,
+                anchor,
+                text,
+                color,
+            } => {
+                let text_anchor = match anchor.x() {
+                    egui::Align::Min => "start",
+                    egui::Align::Center => "middle",
+                    egui::Align::Max => "end",
+                };
+                let baseline = match anchor.y() {
+                    egui::Align::Min => "hanging",
+                    egui::Align::Center => "central",
+                    egui::Align::Max => "alphabetic",
+                };
+                svg += &format!(
+                    "<text x=\"{:.2}\" y=\"{:.2}\" text-anchor=\"{}\" dominant-baseline=\"{}\" fill=\"{}\">{}</text>\n",
+                    pos.x,
+                    pos.y,
+                    text_anchor,
+                    baseline,
+                    svg_color(color),
+                    escape_xml(&text)
+                );
+            }
+            Primitive::Rect { rect, fill, stroke } => {
+                svg += &format!(
+                    "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"{}\" fill-opacity=\"{}\" stroke=\"{}\" stroke-opacity=\"{}\"/>\n",
+                    rect.min.x,
+                    rect.min.y,
+                    rect.width(),
+                    rect.height(),
+                    svg_color(fill),
+                    svg_opacity(fill),
+                    svg_color(stroke),
+                    svg_opacity(stroke)
+                );
+            }
+            Primitive::Circle {
+                center,
+                radius,
+                color,
+            } => {
+                svg += &format!(
+                    "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"{}\" fill=\"{}\" fill-opacity=\"{}\"/>\n",
+                    center.x,
+                    center.y,
+                    radius,
+                    svg_color(color),
+                    svg_opacity(color)
+                );
+            }
+        }
+    }
+    svg += "</svg>\n";
+    return svg;
+}
+
+//drawn on the cpu with egui's own tessellator and font atlas, so it needs no gpu and works in the browser
+pub fn to_png(image: &PlotImage, width: u32, height: u32) -> Result<Vec<u8>, String> {
+    if width == 0 || height == 0 {
+        return Err("Image size must be above 0".to_string());
+    }
+    if width > MAX_PNG_SIZE || height > MAX_PNG_SIZE {
+        return Err(format!("Image size can't be above {} px", MAX_PNG_SIZE));
+    }
+    let pixels_per_point = width as f32 / WIDTH;
+    let size = egui::vec2(WIDTH, height as f32 / pixels_per_point);
+
+    let fonts = epaint::text::Fonts::new(pixels_per_point, 8192, egui::FontDefinitions::default());
+    let mut shapes = vec![];
+    for primitive in layout(image, size) {
+        match primitive {
+            Primitive::Polyline {
+                points,
+                color,
+                width,
+            } => shapes.push(epaint::Shape::line(
+                points,
+                epaint::Stroke::new(width, color),
+            )),
+            Primitive::Text {
+                pos,
+                anchor,
+                text,
+                color,
+            } => shapes.push(epaint::Shape::text(
+                &fonts,
+                pos,
+                anchor,
+                text,
+                egui::FontId::proportional(FONT_SIZE),
+                color,
+            )),
+            Primitive::Rect { rect, fill, stroke } => {
+                shapes.push(epaint::Shape::rect_filled(rect, 0.0, fill));
+                if stroke != Color32::TRANSPARENT {
+                    shapes.push(epaint::Shape::closed_line(
+                        vec![
+                            rect.left_top(),
+                            rect.right_top(),
+                            rect.right_bottom(),
+                            rect.left_bottom(),
+                        ],
+                        epaint::Stroke::new(1.0, stroke),
+                    ));
+                }
+            }
+            Primitive::Circle {
+                center,
+                radius,
+                color,
+            } => shapes.push(epaint::Shape::circle_filled(center, radius, color)),
+        }
+    }
+
+    //text layout above fills the atlas, read it afterwards
+    let font_image = fonts.image();
+    let prepared_discs = fonts.texture_atlas().lock().prepared_discs();
+    let mut tessellator = epaint::Tessellator::new(
+        pixels_per_point,
+        epaint::TessellationOptions::default(),
+        font_image.size,
+        prepared_discs,
+    );
+    let mut mesh = epaint::Mesh::default();
+    for shape in shapes {
+        tessellator.tessellate_shape(shape, &mut mesh);
+    }
+
+    let (width, height) = (width as usize, height as usize);
+    //rgba bytes, encoded as they are so only one image sized buffer exists
+    let mut pixels = BACKGROUND.to_array().repeat(width * height);
+    for triangle in mesh.indices.chunks_exact(3) {
+        let vertices = [0, 1, 2].map(|corner| &mesh.vertices[triangle[corner] as usize]);
+        rasterize_triangle(
+            &mut pixels,
+            width,
+            height,
+            pixels_per_point,
+            vertices,
+            &font_image,
+        );
+    }
+
+    let mut png = vec![];
+    let mut encoder = png::Encoder::new(&mut png, width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&pixels))
+        .map_err(|err| err.to_string())?;
+    return Ok(png);
+}
+
+//blends one triangle of premultiplied colors onto the rgba bytes in `pixels`, the font atlas gives the coverage
+fn rasterize_triangle(
+    pixels: &mut [u8],
+    width: usize,
+    height: usize,
+    pixels_per_point: f32,
+    vertices: [&epaint::Vertex; 3],
+    font_image: &epaint::FontImage,
+) {
+    let positions = vertices.map(|vertex| vertex.pos.to_vec2() * pixels_per_point);
+    let edge = |a: egui::Vec2, b: egui::Vec2, point: egui::Vec2| -> f32 {
+        (b.x - a.x) * (point.y - a.y) - (b.y - a.y) * (point.x - a.x)
+    };
+    let area = edge(positions[0], positions[1], positions[2]);
+    if area == 0.0 {
+        return;
+    }
+
+    let min_x = positions
+        .iter()
+        .map(|p| p.x)
+        .fold(f32::MAX, f32::min)
+        .floor()
+        .max(0.0) as usize;
+    let max_x = (positions
+        .iter()
+        .map(|p| p.x)
+        .fold(f32::MIN, f32::max)
+        .ceil() as usize)
+        .min(width);
+    let min_y = positions
+        .iter()
+        .map(|p| p.y)
+        .fold(f32::MAX, f32::min)
+        .floor()
+        .max(0.0) as usize;
+    let max_y = (positions
+        .iter()
+        .map(|p| p.y)
+        .fold(f32::MIN, f32::max)
+        .ceil() as usize)
+        .min(height);
+    let colors = vertices.map(|vertex| {
+        vertex
+            .color
+            .to_array()
+            .map(|channel| channel as f32 / 255.0)
+    });
+
+    for y in min_y..max_y {
+        for x in min_x..max_x {
+            let point = egui::vec2(x as f32 + 0.5, y as f32 + 0.5);
+            let weights = [
+                edge(positions[1], positions[2], point) / area,
+                edge(positions[2], positions[0], point) / area,
+                edge(positions[0], positions[1], point) / area,
+            ];
+            if weights.iter().any(|weight| *weight < 0.0) {
+                continue;
+            }
+            let uv = vertices
+                .iter()
+                .zip(weights)
+                .fold(egui::Vec2::ZERO, |uv, (vertex, weight)| {
+                    uv + vertex.uv.to_vec2() * weight
+                });
+            let texel_x = ((uv.x * font_image.size[0] as f32) as usize).min(font_image.size[0] - 1);
+            let texel_y = ((uv.y * font_image.size[1] as f32) as usize).min(font_image.size[1] - 1);
+            let coverage = font_image.pixels[texel_y * font_image.size[0] + texel_x];
+
+            let mut source = [0.0; 4];
+            for (color, weight) in colors.iter().zip(weights) {
+                for channel in 0..4 {
+                    source[channel] += color[channel] * weight * coverage;
+                }
+            }
+            let destination = &mut pixels[(y * width + x) * 4..][..4];
+            for channel in 0..4 {
+                let blended =
+                    source[channel] + destination[channel] as f32 / 255.0 * (1.0 - source[3]);
+                destination[channel] = (blended.clamp(0.0, 1.0) * 255.0).round() as u8;
+            }
+        }
+    }
+}
+
+fn layout(image: &PlotImage, size: egui::Vec2) -> Vec<Primitive> {
+    let pane_height = size.y / image.panes.len().max(1) as f32;
+    let mut primitives = vec![];
+    for (i, pane) in image.panes.iter().enumerate() {
+        let pane_rect = Rect::from_min_size(
+            egui::pos2(0.0, i as f32 * pane_height),
+            egui::vec2(size.x, pane_height),
+        );
+        primitives.extend(layout_pane(pane, pane_rect));
+    }
+    return primitives;
+}
+
+fn layout_pane(pane: &ImagePane, pane_rect: Rect) -> Vec<Primitive> {
+    let secondary_y_label = match pane.secondary_axes {
+        true => pane.transform.secondary_y_label(),
+        false => None,
+    };
+    let margin_top = match pane.secondary_axes {
+        true => SECONDARY_AXIS_MARGIN,
+        false => MARGIN_TOP,
+    };
+    let margin_right = match secondary_y_label {
+        Some(_) => SECONDARY_AXIS_MARGIN,
+        None => MARGIN_RIGHT,
+    };
+    let plot_rect = Rect::from_min_max(
+        pane_rect.min + egui::vec2(MARGIN_LEFT, margin_top),
+        pane_rect.max - egui::vec2(margin_right, MARGIN_BOTTOM),
+    );
+    let ([min_x, min_y], [max_x, max_y]) = pane.bounds;
+    let to_screen = |[x, y]: [f64; 2]| -> Pos2 {
+        egui::pos2(
+            plot_rect.left() + ((x - min_x) / (max_x - min_x)) as f32 * plot_rect.width(),
+            plot_rect.bottom() - ((y - min_y) / (max_y - min_y)) as f32 * plot_rect.height(),
+        )
+    };
+    let transform = pane.transform;
+    let mut primitives = vec![];
+
+    for (value, label) in ticks(min_x, max_x, transform.units.log_x, |x| transform.axis_x(x)) {
+        let x = to_screen([value, min_y]).x;
+        primitives.push(Primitive::Polyline {
+            points: vec![
+                egui::pos2(x, plot_rect.top()),
+                egui::pos2(x, plot_rect.bottom()),
+            ],
+            color: GRID,
+            width: 1.0,
+        });
+        primitives.push(Primitive::Text {
+            pos: egui::pos2(x, plot_rect.bottom() + 4.0),
+            anchor: egui::Align2::CENTER_TOP,
+            text: label,
+            color: TEXT,
+        });
+        if pane.secondary_axes {
+            let secondary_input = transform.secondary_input();
+            let speed =
+                secondary_input.speed_from_counts_per_ms(transform.inverse_x(value), transform.dpi);
+            primitives.push(Primitive::Text {
+                pos: egui::pos2(x, plot_rect.top() - 4.0),
+                anchor: egui::Align2::CENTER_BOTTOM,
+                text: format!("{:.1}", speed),
+                color: TEXT,
+            });
+        }
+    }
+    for (value, label) in ticks(min_y, max_y, transform.units.log_y, |y| transform.axis_y(y)) {
+        let y = to_screen([min_x, value]).y;
+        primitives.push(Primitive::Polyline {
+            points: vec![
+                egui::pos2(plot_rect.left(), y),
+                egui::pos2(plot_rect.right(), y),
+            ],
+            color: GRID,
+            width: 1.0,
+        });
+        primitives.push(Primitive::Text {
+            pos: egui::pos2(plot_rect.left() - 4.0, y),
+            anchor: egui::Align2::RIGHT_CENTER,
+            text: label,
+            color: TEXT,
+        });
+        if let (Some(_), Some(secondary_y)) = (secondary_y_label, transform.secondary_y(value)) {
+            if secondary_y.is_finite() {
+                primitives.push(Primitive::Text {
+                    pos: egui::pos2(plot_rect.right() + 4.0, y),
+                    anchor: egui::Align2::LEFT_CENTER,
+                    text: format!("{:.2}", secondary_y),
+                    color: TEXT,
+                });
+            }
+        }
+    }
+    primitives.push(Primitive::Polyline {
+        points: vec![
+            plot_rect.left_top(),
+            plot_rect.left_bottom(),
+            plot_rect.right_bottom(),
+        ],
+        color: AXIS,
+        width: 1.0,
+    });
+    primitives.push(Primitive::Text {
+        pos: egui::pos2(plot_rect.center().x, pane_rect.bottom() - 6.0),
+        anchor: egui::Align2::CENTER_BOTTOM,
+        text: pane.x_label.clone(),
+        color: TEXT,
+    });
+    //axis names sit above the tick labels, the secondary ones on the same row
+    let label_y = plot_rect.top() - margin_top + 6.0;
+    primitives.push(Primitive::Text {
+        pos: egui::pos2(pane_rect.left() + 6.0, label_y),
+        anchor: egui::Align2::LEFT_TOP,
+        text: pane.y_label.clone(),
+        color: TEXT,
+    });
+    if pane.secondary_axes {
+        primitives.push(Primitive::Text {
+            pos: egui::pos2(plot_rect.center().x, label_y),
+            anchor: egui::Align2::CENTER_TOP,
+            text: transform.secondary_input().label().to_string(),
+            color: TEXT,
+        });
+    }
+    if let Some(label) = secondary_y_label {
+        primitives.push(Primitive::Text {
+            pos: egui::pos2(pane_rect.right() - 6.0, label_y),
+            anchor: egui::Align2::RIGHT_TOP,
+            text: label.to_string(),
+            color: TEXT,
+        });
+    }
+
+    for area in &pane.areas {
+        let rect =
+            Rect::from_two_pos(to_screen(area.min), to_screen(area.max)).intersect(plot_rect);
+        if rect.is_positive() {
+            primitives.push(Primitive::Rect {
+                rect,
+                fill: area.fill,
+                stroke: area.stroke,
+            });
+        }
+    }
+
+    for line in &pane.lines {
+        let screen_points = line
+            .points
+            .iter()
+            .map(|point| to_screen(*point))
+            .collect::<Vec<Pos2>>();
+        if line.style == LineStyle::Markers {
+            for center in screen_points
+                .into_iter()
+                .filter(|point| plot_rect.contains(*point))
+            {
+                primitives.push(Primitive::Circle {
+                    center,
+                    radius: MARKER_RADIUS,
+                    color: line.color,
+                });
+            }
+            continue;
+        }
+        for points in clip_polyline(&screen_points, plot_rect) {
+            let runs = match line.style {
+                LineStyle::Dashed => dash_polyline(&points),
+                _ => vec![points],
+            };
+            for points in runs {
+                primitives.push(Primitive::Polyline {
+                    points,
+                    color: line.color,
+                    width: 2.0,
+                });
+            }
+        }
+    }
+
+    //legend in the top left corner of the plot, repeated names like histogram bars share a row
+    let mut legend: Vec<(&str, Color32, Option<LineStyle>)> = vec![];
+    let named = pane
+        .areas
+        .iter()
+        .map(|area| (area.name.as_str(), area.fill, None))
+        .chain(
+            pane.lines
+                .iter()
+                .map(|line| (line.name.as_str(), line.color, Some(line.style))),
+        );
+    for (name, color, style) in named {
+        if !name.is_empty()
+            && legend
+                .iter()
+                .all(|(legend_name, _, _)| *legend_name != name)
+        {
+            legend.push((name, color, style));
+        }
+    }
+    if !legend.is_empty() {
+        let row_height = FONT_SIZE + 4.0;
+        let longest_name = legend
+            .iter()
+            .map(|(name, _, _)| name.chars().count())
+            .max()
+            .unwrap_or_default();
+        let legend_rect = Rect::from_min_size(
+            plot_rect.left_top() + egui::vec2(8.0, 8.0),
+            egui::vec2(
+                30.0 + longest_name as f32 * FONT_SIZE * 0.6,
+                legend.len() as f32 * row_height + 8.0,
+            ),
+        );
+        primitives.push(Primitive::Rect {
+            rect: legend_rect,
+            fill: BACKGROUND,
+            stroke: AXIS,
+        });
+        for (i, (name, color, style)) in legend.into_iter().enumerate() {
+            let y = legend_rect.top() + 4.0 + (i as f32 + 0.5) * row_height;
+            let (start, end) = (legend_rect.left() + 6.0, legend_rect.left() + 22.0);
+            match style {
+                None => primitives.push(Primitive::Rect {
+                    rect: Rect::from_min_max(egui::pos2(start, y - 5.0), egui::pos2(end, y + 5.0)),
+                    fill: color,
+                    stroke: Color32::TRANSPARENT,
+                }),
+                Some(LineStyle::Markers) => primitives.push(Primitive::Circle {
+                    center: egui::pos2((start + end) / 2.0, y),
+                    radius: MARKER_RADIUS,
+                    color,
+                }),
+                Some(_) => primitives.push(Primitive::Polyline {
+                    points: vec![egui::pos2(start, y), egui::pos2(end, y)],
+                    color,
+                    width: 2.0,
+                }),
+            }
+            primitives.push(Primitive::Text {
+                pos: egui::pos2(legend_rect.left() + 28.0, y),
+                anchor: egui::Align2::LEFT_CENTER,
+                text: name.to_string(),
+                color: TEXT,
+            });
+        }
+    }
+    return primitives;
+}
+
+//evenly spaced tick positions in plot coordinates with their labels in the plotted unit
+fn ticks(min: f64, max: f64, log: bool, axis_value: impl Fn(f64) -> f64) -> Vec<(f64, String)> {
+    let range = max - min;
+    if range <= 0.0 || !range.is_finite() {
+        return vec![];
+    }
+    //1, 2 or 5 times a power of 10
+    let rough_step = range / TARGET_TICKS;
+    let magnitude = 10f64.powf(rough_step.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .iter()
+        .map(|multiple| multiple * magnitude)
+        .find(|step| *step >= rough_step)
+        .unwrap_or(10.0 * magnitude);
+    let decimals = (-step.log10().floor()).max(0.0) as usize;
+
+    let mut ticks = vec![];
+    let mut value = (min / step).ceil() * step;
+    while value <= max + step * 1e-9 {
+        let label = match log {
+            true => format!("{:.2e}", axis_value(value))
+                .parse::<f64>()
+                .unwrap_or_default()
+                .to_string(),
+            false => format!("{:.*}", decimals, axis_value(value)),
+        };
+        ticks.push((value, label));
+        value += step;
+    }
+    return ticks;
+}
+
+//splits a polyline into the runs that are inside `rect`, cutting segments at its edges
+fn clip_polyline(points: &[Pos2], rect: Rect) -> Vec<Vec<Pos2>> {
+    let mut runs = vec![];
+    let mut run: Vec<Pos2> = vec![];
+    for pair in points.windows(2) {
+        match clip_segment(pair[0], pair[1], rect) {
+            Some((start, end)) => {
+                if run.last() != Some(&start) {
+                    if run.len() > 1 {
+                        runs.push(std::mem::take(&mut run));
+                    }
+                    run = vec![start];
+                }
+                run.push(end);
+            }
+            None => {
+                if run.len() > 1 {
+                    runs.push(std::mem::take(&mut run));
+                }
+                run.clear();
+            }
+        }
+    }
+    if run.len() > 1 {
+        runs.push(run);
+    }
+    return runs;
+}
+
+//splits a polyline into dashes of `DASH_LENGTH` with `DASH_GAP` between them
+fn dash_polyline(points: &[Pos2]) -> Vec<Vec<Pos2>> {
+    let mut dashes = vec![];
+    let mut dash: Vec<Pos2> = vec![];
+    //distance into the current dash and gap cycle
+    let mut position: f32 = 0.0;
+    for pair in points.windows(2) {
+        let (start, end) = (pair[0], pair[1]);
+        let length = (end - start).length();
+        let mut travelled: f32 = 0.0;
+        while travelled < length {
+            let drawing = position < DASH_LENGTH;
+            let remaining = match drawing {
+                true => DASH_LENGTH - position,
+                false => DASH_LENGTH + DASH_GAP - position,
+            };
+            let step = remaining.min(length - travelled);
+            let from = start + (end - start) * (travelled / length);
+            let to = start + (end - start) * ((travelled + step) / length);
+            if drawing {
+                if dash.is_empty() {
+                    dash.push(from);
+                }
+                dash.push(to);
+            }
+            travelled += step;
+            position += step;
+            if position >= DASH_LENGTH && dash.len() > 1 {
+                dashes.push(std::mem::take(&mut dash));
+            }
+            if position >= DASH_LENGTH + DASH_GAP {
+                position = 0.0;
+            }
+        }
+    }
+    if dash.len() > 1 {
+        dashes.push(dash);
+    }
+    return dashes;
+}
+
+//liang-barsky
+fn clip_segment(start: Pos2, end: Pos2, rect: Rect) -> Option<(Pos2, Pos2)> {
+    let delta = end - start;
+    let mut t_start: f32 = 0.0;
+    let mut t_end: f32 = 1.0;
+    for (p, q) in [
+        (-delta.x, start.x - rect.left()),
+        (delta.x, rect.right() - start.x),
+        (-delta.y, start.y - rect.top()),
+        (delta.y, rect.bottom() - start.y),
+    ] {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None;
+            }
+            continue;
+        }
+        let t = q / p;
+        if p < 0.0 {
+            t_start = t_start.max(t);
+        } else {
+            t_end = t_end.min(t);
+        }
+    }
+    if t_start > t_end {
+        return None;
+    }
+    return Some((start + delta * t_start, start + delta * t_end));
+}
+
+fn svg_color(color: Color32) -> String {
+    let [r, g, b, _] = color.to_srgba_unmultiplied();
+    return format!("rgb({},{},{})", r, g, b);
+}
+
+fn svg_opacity(color: Color32) -> String {
+    return format!("{:.3}", color.a() as f32 / 255.0);
+}
+
+fn escape_xml(text: &str) -> String {
+    return text
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::units::PlotUnits;
+
+    fn pane(secondary_axes: bool) -> ImagePane {
+        return ImagePane {
+            transform: PlotTransform {
+                units: PlotUnits::default(),
+                dpi: 1600,
+                yaw: 0.022,
+                game_sens: 1.0,
+            },
+            bounds: ([0.0, 0.0], [10.0, 2.0]),
+            x_label: "Input Speed (counts/ms)".to_string(),
+            y_label: "Sens".to_string(),
+            secondary_axes,
+            areas: vec![PlotArea {
+                name: "Velocity Decreasing".to_string(),
+                fill: Color32::from_rgba_unmultiplied(220, 60, 60, 40),
+                stroke: Color32::TRANSPARENT,
+                min: [2.0, 0.0],
+                max: [4.0, 2.0],
+            }],
+            lines: vec![PlotLine {
+                name: "Comparison".to_string(),
+                color: Color32::from_rgb(200, 120, 60),
+                style: LineStyle::Dashed,
+                points: vec![[0.0, 1.0], [10.0, 1.0]],
+            }],
+        };
+    }
+
+    #[test]
+    fn dashes() {
+        let dashes = dash_polyline(&[egui::pos2(0.0, 0.0), egui::pos2(30.0, 0.0)]);
+        let ends = dashes
+            .iter()
+            .map(|dash| (dash.first().unwrap().x, dash.last().unwrap().x))
+            .collect::<Vec<(f32, f32)>>();
+        assert_eq!(ends, vec![(0.0, 8.0), (14.0, 22.0), (28.0, 30.0)]);
+    }
+
+    #[test]
+    fn secondary_axes_are_drawn() {
+        let with = to_svg(
+            &PlotImage {
+                panes: vec![pane(true)],
+            },
+            1.5,
+        );
+        let without = to_svg(
+            &PlotImage {
+                panes: vec![pane(false)],
+            },
+            1.5,
+        );
+        assert!(with.contains(">cm/s</text>") && with.contains(">cm/360</text>"));
+        assert!(!without.contains(">cm/s</text>") && !without.contains(">cm/360</text>"));
+    }
+
+    #[test]
+    fn areas_and_legend() {
+        let svg = to_svg(
+            &PlotImage {
+                panes: vec![pane(false)],
+            },
+            1.5,
+        );
+        assert!(svg.contains("fill=\"rgb(220,60,60)\" fill-opacity=\"0.157\""));
+        assert!(svg.contains(">Velocity Decreasing</text>") && svg.contains(">Comparison</text>"));
+    }
+
+    #[test]
+    fn png_size_limits() {
+        let image = PlotImage {
+            panes: vec![pane(true)],
+        };
+        let png = to_png(&image, 400, 300).unwrap();
+        assert_eq!(&png[1..4], b"PNG");
+        assert!(to_png(&image, 0, 300).is_err());
+        assert!(to_png(&image, MAX_PNG_SIZE + 1, 300).is_err());
+    }
+
+    #[test]
+    fn stacked_panes_split_the_height() {
+        let image = PlotImage {
+            panes: vec![pane(false), pane(false)],
+        };
+        let bottom_labels = layout(&image, egui::vec2(WIDTH, 600.0))
+            .into_iter()
+            .filter_map(|primitive| match primitive {
+                Primitive::Text { pos, text, .. } if text.starts_with("Input Speed") => Some(pos.y),
+                _ => None,
+            })
+            .collect::<Vec<f32>>();
+        assert_eq!(bottom_labels, vec![294.0, 594.0]);
+    }
+}
@@ -0,0 +1,724 @@
+use std::ops::{Add, Div, Mul, Sub};
+
+use rawaccel_convert::types::{AccelArgs, AccelMode, CapMode, PointScaling};
+
+use crate::curve_analysis;
+use crate::fit;
+use crate::gui::{cap_speed, try_get_point};
+use crate::parameters;
+
+//closed forms have to match rawaccel_convert this closely at every checked speed to be exported
+const CLOSED_FORM_TOLERANCE: f64 = 1e-4;
+const CLOSED_FORM_CHECKS: usize = 256;
+const MATPLOTLIB_SAMPLES: usize = 1000;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum ScriptFormat {
+    Gnuplot,
+    Matplotlib,
+    Desmos,
+}
+
+impl ScriptFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ScriptFormat::Gnuplot => "gnuplot",
+            ScriptFormat::Matplotlib => "Python (matplotlib)",
+            ScriptFormat::Desmos => "Desmos",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ScriptFormat::Gnuplot => "gp",
+            ScriptFormat::Matplotlib => "py",
+            ScriptFormat::Desmos => "txt",
+        }
+    }
+}
+
+//sensitivity as a function of input speed in counts/ms, written out in each script's own syntax
+#[derive(Clone, PartialEq, Debug)]
+enum Expr {
+    Speed,
+    Number(f64),
+    Add(Box<Expr>, Box<Expr>),
+    Sub(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+    Div(Box<Expr>, Box<Expr>),
+    Pow(Box<Expr>, Box<Expr>),
+    Exp(Box<Expr>),
+    Ln(Box<Expr>),
+    Tanh(Box<Expr>),
+    Min(Box<Expr>, Box<Expr>),
+    Max(Box<Expr>, Box<Expr>),
+    //`below` for speeds under the threshold, `above` from it on
+    Below {
+        threshold: f64,
+        below: Box<Expr>,
+        above: Box<Expr>,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Syntax {
+    Gnuplot,
+    Numpy,
+    Desmos,
+}
+
+fn number(value: f64) -> Expr {
+    return Expr::Number(value);
+}
+
+impl Add for Expr {
+    type Output = Expr;
+    fn add(self, other: Expr) -> Expr {
+        match (&self, &other) {
+            (_, Expr::Number(zero)) if *zero == 0.0 => return self,
+            (Expr::Number(zero), _) if *zero == 0.0 => return other,
+            _ => return Expr::Add(Box::new(self), Box::new(other)),
+        }
+    }
+}
+
+impl Sub for Expr {
+    type Output = Expr;
+    fn sub(self, other: Expr) -> Expr {
+        match &other {
+            Expr::Number(zero) if *zero == 0.0 => return self,
+            _ => return Expr::Sub(Box::new(self), Box::new(other)),
+        }
+    }
+}
+
+impl Mul for Expr {
+    type Output = Expr;
+    fn mul(self, other: Expr) -> Expr {
+        match (&self, &other) {
+            (_, Expr::Number(one)) if *one == 1.0 => return self,
+            (Expr::Number(one), _) if *one == 1.0 => return other,
+            _ => return Expr::Mul(Box::new(self), Box::new(other)),
+        }
+    }
+}
+
+impl Div for Expr {
+    type Output = Expr;
+    fn div(self, other: Expr) -> Expr {
+        match &other {
+            Expr::Number(one) if *one == 1.0 => return self,
+            _ => return Expr::Div(Box::new(self), Box::new(other)),
+        }
+    }
+}
+
+impl Expr {
+    fn pow(self, exponent: Expr) -> Expr {
+        match &exponent {
+            Expr::Number(one) if *one == 1.0 => return self,
+            _ => return Expr::Pow(Box::new(self), Box::new(exponent)),
+        }
+    }
+
+    fn exp(self) -> Expr {
+        return Expr::Exp(Box::new(self));
+    }
+
+    fn ln(self) -> Expr {
+        return Expr::Ln(Box::new(self));
+    }
+
+    fn tanh(self) -> Expr {
+        return Expr::Tanh(Box::new(self));
+    }
+
+    fn min(self, other: Expr) -> Expr {
+        return Expr::Min(Box::new(self), Box::new(other));
+    }
+
+    fn max(self, other: Expr) -> Expr {
+        return Expr::Max(Box::new(self), Box::new(other));
+    }
+
+    fn value(&self, speed: f64) -> f64 {
+        match self {
+            Expr::Speed => return speed,
+            Expr::Number(value) => return *value,
+            Expr::Add(a, b) => return a.value(speed) + b.value(speed),
+            Expr::Sub(a, b) => return a.value(speed) - b.value(speed),
+            Expr::Mul(a, b) => return a.value(speed) * b.value(speed),
+            Expr::Div(a, b) => return a.value(speed) / b.value(speed),
+            Expr::Pow(a, b) => return a.value(speed).powf(b.value(speed)),
+            Expr::Exp(a) => return a.value(speed).exp(),
+            Expr::Ln(a) => return a.value(speed).ln(),
+            Expr::Tanh(a) => return a.value(speed).tanh(),
+            Expr::Min(a, b) => return a.value(speed).min(b.value(speed)),
+            Expr::Max(a, b) => return a.value(speed).max(b.value(speed)),
+            Expr::Below {
+                threshold,
+                below,
+                above,
+            } => {
+                return match speed < *threshold {
+                    true => below.value(speed),
+                    false => above.value(speed),
+                };
+            }
+        }
+    }
+
+    //fully parenthesized so precedence never differs between the targets
+    fn render(&self, syntax: Syntax) -> String {
+        let function = |name: &str, a: &Expr| -> String {
+            return match syntax {
+                Syntax::Gnuplot => format!("{}({})", name, a.render(syntax)),
+                Syntax::Numpy => format!("np.{}({})", name, a.render(syntax)),
+                Syntax::Desmos => {
+                    let name = match name {
+                        "log" => "ln",
+                        _ => name,
+                    };
+                    format!("\\{}\\left({}\\right)", name, a.render(syntax))
+                }
+            };
+        };
+        let operator = |a: &Expr, operator: &str, b: &Expr| -> String {
+            return match syntax {
+                Syntax::Desmos => format!(
+                    "\\left({}{}{}\\right)",
+                    a.render(syntax),
+                    operator,
+                    b.render(syntax)
+                ),
+                _ => format!("({} {} {})", a.render(syntax), operator, b.render(syntax)),
+            };
+        };
+        let extreme = |name: &str, comparison: &str, a: &Expr, b: &Expr| -> String {
+            let (a, b) = (a.render(syntax), b.render(syntax));
+            return match syntax {
+                //gnuplot has no min or max function
+                Syntax::Gnuplot => format!("({} {} {} ? {} : {})", a, comparison, b, a, b),
+                Syntax::Numpy => format!("np.{}imum({}, {})", name, a, b),
+                Syntax::Desmos => format!("\\{}\\left({},{}\\right)", name, a, b),
+            };
+        };
+        match self {
+            Expr::Speed => return "x".to_string(),
+            Expr::Number(value) => return format_number(*value),
+            Expr::Add(a, b) => return operator(a, "+", b),
+            Expr::Sub(a, b) => return operator(a, "-", b),
+            Expr::Mul(a, b) => match syntax {
+                Syntax::Desmos => return operator(a, "\\cdot ", b),
+                _ => return operator(a, "*", b),
+            },
+            Expr::Div(a, b) => match syntax {
+                Syntax::Desmos => {
+                    return format!("\\frac{{{}}}{{{}}}", a.render(syntax), b.render(syntax))
+                }
+                _ => return operator(a, "/", b),
+            },
+            Expr::Pow(a, b) => match syntax {
+                Syntax::Desmos => {
+                    return format!(
+                        "\\left({}\\right)^{{{}}}",
+                        a.render(syntax),
+                        b.render(syntax)
+                    )
+                }
+                _ => return operator(a, "**", b),
+            },
+            Expr::Exp(a) => return function("exp", a),
+            Expr::Ln(a) => return function("log", a),
+            Expr::Tanh(a) => return function("tanh", a),
+            Expr::Min(a, b) => return extreme("min", "<", a, b),
+            Expr::Max(a, b) => return extreme("max", ">", a, b),
+            Expr::Below {
+                threshold,
+                below,
+                above,
+            } => {
+                let (threshold, below, above) = (
+                    format_number(*threshold),
+                    below.render(syntax),
+                    above.render(syntax),
+                );
+                return match syntax {
+                    Syntax::Gnuplot => format!("(x < {} ? {} : {})", threshold, below, above),
+                    Syntax::Numpy => format!("np.where(x < {}, {}, {})", threshold, below, above),
+                    Syntax::Desmos => {
+                        format!("\\left\\{{x<{}:{},{}\\right\\}}", threshold, below, above)
+                    }
+                };
+            }
+        }
+    }
+}
+
+//always with a decimal point, `1/2` is integer division in gnuplot
+fn format_number(value: f64) -> String {
+    let mut text = value.abs().to_string();
+    if !text.contains('.') {
+        text += ".0";
+    }
+    match value < 0.0 {
+        true => return format!("(-{})", text),
+        false => return text,
+    }
+}
+
+//sens at `speed` before the cap and sens multiplier, `None` when rawaccel_convert has no closed form for the mode
+fn base_sens(args: &AccelArgs, speed: Expr) -> Option<Expr> {
+    let offset_speed = (speed.clone() - number(args.input_offset)).max(number(0.0));
+    match (&args.mode, args.gain) {
+        (AccelMode::Noaccel, _) => return Some(number(1.0)),
+        (AccelMode::Linear | AccelMode::Classic, gain) => {
+            let power = match args.mode {
+                AccelMode::Linear => 2.0,
+                _ => args.exponent_classic,
+            };
+            //with both cap values the curve is fit through the cap point instead of using acceleration
+            let rate = match args.cap_mode {
+                CapMode::InputOutput => {
+                    (args.cap.y - 1.0) / (args.cap.x - args.input_offset).powf(power - 1.0)
+                }
+                _ => args.acceleration.powf(power - 1.0),
+            };
+            return Some(match gain {
+                false => number(1.0) + number(rate) * offset_speed.pow(number(power - 1.0)),
+                //velocity is the integral of the gain curve 1 + rate * offset_speed^(power - 1)
+                true => {
+                    number(1.0) + number(rate / power) * offset_speed.pow(number(power)) / speed
+                }
+            });
+        }
+        (AccelMode::Natural, gain) => {
+            let limit = args.limit - 1.0;
+            let decay = (number(-args.decay_rate) * offset_speed.clone()).exp();
+            return Some(match gain {
+                false => number(1.0) + number(limit) * (number(1.0) - decay),
+                true => {
+                    number(1.0)
+                        + number(limit)
+                            * (offset_speed + decay / number(args.decay_rate)
+                                - number(1.0 / args.decay_rate))
+                            / speed
+                }
+            });
+        }
+        (AccelMode::Power, gain) => {
+            let scale = match args.cap_mode {
+                CapMode::InputOutput => {
+                    (args.cap.y - args.output_offset).powf(1.0 / args.exponent_power) / args.cap.x
+                }
+                _ => args.scale,
+            };
+            let power = (number(scale) * speed).pow(number(args.exponent_power));
+            return Some(match gain {
+                false => number(args.output_offset) + power,
+                true => number(args.output_offset) + power / number(args.exponent_power + 1.0),
+            });
+        }
+        (AccelMode::Jump, gain) => {
+            let jump = args.cap.y - 1.0;
+            if args.smooth == 0.0 {
+                let above = match gain {
+                    false => number(args.cap.y),
+                    true => {
+                        number(1.0) + number(jump) * (speed.clone() - number(args.cap.x)) / speed
+                    }
+                };
+                return Some(Expr::Below {
+                    threshold: args.cap.x,
+                    below: Box::new(number(1.0)),
+                    above: Box::new(above),
+                });
+            }
+            let rate = std::f64::consts::TAU / (args.cap.x * args.smooth);
+            let decay = (number(rate) * (number(args.cap.x) - speed.clone())).exp();
+            return Some(match gain {
+                false => number(1.0) + number(jump) / (number(1.0) + decay),
+                true => {
+                    let start = (rate * args.cap.x).exp().ln_1p() / rate;
+                    number(1.0)
+                        + number(jump)
+                            * (speed.clone() + (number(1.0) + decay).ln() / number(rate)
+                                - number(start))
+                            / speed
+                }
+            });
+        }
+        (AccelMode::Synchronous, false) => {
+            let motivity = number(args.motivity);
+            if args.smooth == 0.0 {
+                let power = (speed / number(args.sync_speed)).pow(number(args.gamma));
+                return Some(power.max(number(1.0 / args.motivity)).min(motivity));
+            }
+            let log_motivity = args.motivity.ln();
+            let sharpness = 0.5 / args.smooth;
+            let side = |ratio: Expr, sign: f64| -> Expr {
+                let log_space = number(args.gamma / log_motivity) * ratio.ln();
+                return (number(sign * log_motivity)
+                    * log_space
+                        .pow(number(sharpness))
+                        .tanh()
+                        .pow(number(1.0 / sharpness)))
+                .exp();
+            };
+            return Some(Expr::Below {
+                threshold: args.sync_speed,
+                below: Box::new(side(number(args.sync_speed) / speed.clone(), -1.0)),
+                above: Box::new(side(speed / number(args.sync_speed), 1.0)),
+            });
+        }
+        (AccelMode::Motivity, false) => {
+            let sigmoid = number(2.0)
+                / (number(1.0) + (speed / number(args.sync_speed)).pow(number(-args.gamma)));
+            return Some(number(args.motivity).pow(sigmoid - number(1.0)));
+        }
+        //rawaccel_convert integrates these numerically
+        (AccelMode::Synchronous | AccelMode::Motivity, true) | (AccelMode::Lookup, _) => {
+            return None;
+        }
+    }
+}
+
+//sens with the cap and sens multiplier applied
+fn closed_form(args: &AccelArgs) -> Option<Expr> {
+    let uncapped = base_sens(args, Expr::Speed)?;
+    let capped = match cap_speed(args) {
+        Some(cap) if cap > 0.0 => match args.gain {
+            //the curve just stops rising
+            false => base_sens(args, Expr::Speed.min(number(cap)))?,
+            //velocity keeps rising at the gain reached at the cap
+            true => {
+                let velocity = |speed: f64| -> f64 {
+                    return speed * uncapped.value(speed);
+                };
+                let step = (cap * 1e-6).max(1e-9);
+                let cap_gain = (velocity(cap + step) - velocity(cap - step)) / (2.0 * step);
+                let cap_velocity = velocity(cap);
+                Expr::Below {
+                    threshold: cap,
+                    below: Box::new(uncapped),
+                    above: Box::new(
+                        (number(cap_velocity) + number(cap_gain) * (Expr::Speed - number(cap)))
+                            / Expr::Speed,
+                    ),
+                }
+            }
+        },
+        _ => uncapped,
+    };
+    return Some(number(args.sens_multiplier) * capped);
+}
+
+//only kept when it reproduces rawaccel_convert's curve over the plotted speeds
+fn checked_closed_form(args: &AccelArgs) -> Option<Expr> {
+    let expression = closed_form(args)?;
+    let sens_args = sens_args(args);
+    let max_speed = max_speed(args);
+    for i in 1..=CLOSED_FORM_CHECKS {
+        let speed = max_speed * i as f64 / CLOSED_FORM_CHECKS as f64;
+        let Ok(expected) = try_get_point(speed, &sens_args) else {
+            continue;
+        };
+        if !expected.is_finite() {
+            continue;
+        }
+        let value = expression.value(speed);
+        if !value.is_finite()
+            || (value - expected).abs() > CLOSED_FORM_TOLERANCE * expected.abs().max(1.0)
+        {
+            return None;
+        }
+    }
+    return Some(expression);
+}
+
+//same range as the plot
+fn max_speed(args: &AccelArgs) -> f64 {
+    return (args.dpi / 20).max(1) as f64;
+}
+
+fn sens_args(args: &AccelArgs) -> AccelArgs {
+    let mut sens_args = args.clone();
+    sens_args.point_scaling = PointScaling::Sens;
+    return sens_args;
+}
+
+//(speed, sens) rows, at the table's own speeds for look up tables
+fn data_table(args: &AccelArgs) -> Vec<[f64; 2]> {
+    let sens_args = sens_args(args);
+    if args.mode == AccelMode::Lookup {
+        return args
+            .lookup_data
+            .iter()
+            .filter_map(|point| {
+                let sens = try_get_point(point.x, &sens_args).ok()?;
+                return (point.x.is_finite() && sens.is_finite()).then_some([point.x, sens]);
+            })
+            .collect();
+    }
+    let max_speed = max_speed(args);
+    return curve_analysis::adaptive_samples(|t| {
+        let speed = t * max_speed;
+        return try_get_point(speed, &sens_args)
+            .ok()
+            .map(|sens| [speed, sens]);
+    });
+}
+
+//one line per setting that shapes the curve, for the comment at the top of the scripts
+fn description(args: &AccelArgs) -> Vec<String> {
+    let mut lines = vec![format!("Curve: {}", fit::mode_label(&args.mode, args.gain))];
+    for parameter in [
+        vec![&parameters::SENS_MULTIPLIER],
+        parameters::mode_parameters(args),
+    ]
+    .concat()
+    {
+        lines.push(format!(
+            "{}: {}",
+            parameter.label,
+            fit::format_parameter((parameter.get)(args))
+        ));
+    }
+    lines.push("Sensitivity against input speed in counts/ms".to_string());
+    return lines;
+}
+
+pub fn script(args: &AccelArgs, format: ScriptFormat) -> String {
+    let expression = checked_closed_form(args);
+    let table = match expression {
+        Some(_) => vec![],
+        None => data_table(args),
+    };
+    return match format {
+        ScriptFormat::Gnuplot => gnuplot(args, expression.as_ref(), &table),
+        ScriptFormat::Matplotlib => matplotlib(args, expression.as_ref()),
+        ScriptFormat::Desmos => desmos(expression.as_ref(), &table),
+    };
+}
+
+//false when the scripts fall back to sampled points
+pub fn has_closed_form(args: &AccelArgs) -> bool {
+    return checked_closed_form(args).is_some();
+}
+
+fn gnuplot(args: &AccelArgs, expression: Option<&Expr>, table: &[[f64; 2]]) -> String {
+    let mut script = String::default();
+    for line in description(args) {
+        script += &format!("# {}\n", line);
+    }
+    script += "set xlabel \"Input Speed (counts/ms)\"\n";
+    script += "set ylabel \"Sens\"\n";
+    script += "set grid\n";
+    match expression {
+        Some(expression) => {
+            script += &format!("sens(x) = {}\n", expression.render(Syntax::Gnuplot));
+            script += "set samples 1000\n";
+            script += &format!(
+                "plot [0:{}] sens(x) with lines title \"{}\"\n",
+                format_number(max_speed(args)),
+                fit::mode_label(&args.mode, args.gain)
+            );
+        }
+        None => {
+            script += "$curve << EOD\n";
+            for [x, y] in table {
+                script += &format!("{} {}\n", x, y);
+            }
+            script += "EOD\n";
+            script += &format!(
+                "plot $curve with lines title \"{}\"\n",
+                fit::mode_label(&args.mode, args.gain)
+            );
+        }
+    }
+    return script;
+}
+
+//the sampled points are always embedded, the closed form is plotted over them when there is one
+fn matplotlib(args: &AccelArgs, expression: Option<&Expr>) -> String {
+    let table = data_table(args);
+    let column = |index: usize| -> String {
+        return table
+            .iter()
+            .map(|point| point[index].to_string())
+            .collect::<Vec<String>>()
+            .join(", ");
+    };
+    let mut script = String::default();
+    for line in description(args) {
+        script += &format!("# {}\n", line);
+    }
+    script += "import matplotlib.pyplot as plt\n";
+    script += "import numpy as np\n\n";
+    script += &format!("speed = np.array([{}])\n", column(0));
+    script += &format!("sens = np.array([{}])\n\n", column(1));
+    script += &format!(
+        "plt.plot(speed, sens, label=\"{}\")\n",
+        fit::mode_label(&args.mode, args.gain)
+    );
+    if let Some(expression) = expression {
+        script += &format!(
+            "\n\ndef sens_formula(x):\n    return {}\n\n\n",
+            expression.render(Syntax::Numpy)
+        );
+        script += "with np.errstate(all=\"ignore\"):\n";
+        script += &format!(
+            "    x = np.linspace({}, {}, {})\n",
+            format_number(max_speed(args) / MATPLOTLIB_SAMPLES as f64),
+            format_number(max_speed(args)),
+            MATPLOTLIB_SAMPLES
+        );
+        script += "    plt.plot(x, sens_formula(x), \"--\", label=\"Closed form\")\n";
+    }
+    script += "plt.xlabel(\"Input Speed (counts/ms)\")\n";
+    script += "plt.ylabel(\"Sens\")\n";
+    script += "plt.grid(True)\n";
+    script += "plt.legend()\n";
+    script += "plt.show()\n";
+    return script;
+}
+
+//pasted straight into the expression list, tab separated rows become a table
+fn desmos(expression: Option<&Expr>, table: &[[f64; 2]]) -> String {
+    match expression {
+        Some(expression) => return format!("f(x)={}\n", expression.render(Syntax::Desmos)),
+        None => {
+            return table
+                .iter()
+                .map(|[x, y]| format!("{}\t{}\n", x, y))
+                .collect::<String>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expression;
+
+    //the curve a new user starts from for `mode`
+    fn default_args(mode: AccelMode, gain: bool) -> AccelArgs {
+        let mut args = AccelArgs {
+            mode,
+            gain,
+            ..AccelArgs::default()
+        };
+        for parameter in [
+            vec![&parameters::SENS_MULTIPLIER],
+            parameters::mode_parameters(&args),
+        ]
+        .concat()
+        {
+            (parameter.set)(&mut args, expression::evaluate(parameter.default).unwrap());
+        }
+        return args;
+    }
+
+    //`checked_closed_form` compares each expression against `get_point`, so this depends on the curves of the
+    //rawaccel_convert revision pinned in Cargo.toml and has to be rerun whenever that pin moves
+    #[test]
+    fn closed_forms_accepted_for_defaults() {
+        for mode in [fit::parametric_modes().to_vec(), vec![AccelMode::Noaccel]].concat() {
+            for gain in [false, true] {
+                if gain && matches!(mode, AccelMode::Synchronous | AccelMode::Motivity) {
+                    continue;
+                }
+                let args = default_args(mode.clone(), gain);
+                assert!(
+                    has_closed_form(&args),
+                    "{} closed form was dropped",
+                    fit::mode_label(&args.mode, args.gain)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn integrated_modes_have_no_closed_form() {
+        for (mode, gain) in [
+            (AccelMode::Synchronous, true),
+            (AccelMode::Motivity, true),
+            (AccelMode::Lookup, false),
+            (AccelMode::Lookup, true),
+        ] {
+            assert_eq!(closed_form(&default_args(mode, gain)), None);
+        }
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(format_number(1.0), "1.0");
+        assert_eq!(format_number(0.5), "0.5");
+        assert_eq!(format_number(0.0), "0.0");
+        assert_eq!(format_number(-2.0), "(-2.0)");
+        assert_eq!(format_number(-0.25), "(-0.25)");
+    }
+
+    #[test]
+    fn identities_are_dropped() {
+        assert_eq!(Expr::Speed + number(0.0), Expr::Speed);
+        assert_eq!(number(0.0) + Expr::Speed, Expr::Speed);
+        assert_eq!(Expr::Speed - number(0.0), Expr::Speed);
+        assert_eq!(number(1.0) * Expr::Speed, Expr::Speed);
+        assert_eq!(Expr::Speed / number(1.0), Expr::Speed);
+        assert_eq!(Expr::Speed.pow(number(1.0)), Expr::Speed);
+    }
+
+    #[test]
+    fn render() {
+        let expression = number(1.0) + number(-0.5) * Expr::Speed.pow(number(2.0));
+        assert_eq!(
+            expression.render(Syntax::Gnuplot),
+            "(1.0 + ((-0.5) * (x ** 2.0)))"
+        );
+        assert_eq!(
+            expression.render(Syntax::Numpy),
+            "(1.0 + ((-0.5) * (x ** 2.0)))"
+        );
+        assert_eq!(
+            expression.render(Syntax::Desmos),
+            "\\left(1.0+\\left((-0.5)\\cdot \\left(x\\right)^{2.0}\\right)\\right)"
+        );
+
+        let capped = Expr::Speed.min(number(2.0));
+        assert_eq!(capped.render(Syntax::Gnuplot), "(x < 2.0 ? x : 2.0)");
+        assert_eq!(capped.render(Syntax::Numpy), "np.minimum(x, 2.0)");
+        assert_eq!(capped.render(Syntax::Desmos), "\\min\\left(x,2.0\\right)");
+
+        let log = Expr::Speed.ln();
+        assert_eq!(log.render(Syntax::Gnuplot), "log(x)");
+        assert_eq!(log.render(Syntax::Numpy), "np.log(x)");
+        assert_eq!(log.render(Syntax::Desmos), "\\ln\\left(x\\right)");
+
+        let jump = Expr::Below {
+            threshold: 8.0,
+            below: Box::new(number(1.0)),
+            above: Box::new(number(1.5)),
+        };
+        assert_eq!(jump.render(Syntax::Gnuplot), "(x < 8.0 ? 1.0 : 1.5)");
+        assert_eq!(jump.render(Syntax::Numpy), "np.where(x < 8.0, 1.0, 1.5)");
+        assert_eq!(
+            jump.render(Syntax::Desmos),
+            "\\left\\{x<8.0:1.0,1.5\\right\\}"
+        );
+    }
+
+    #[test]
+    fn values() {
+        let expression = number(1.0) + number(-0.5) * Expr::Speed.pow(number(2.0));
+        assert_eq!(expression.value(2.0), -1.0);
+        let jump = Expr::Below {
+            threshold: 8.0,
+            below: Box::new(number(1.0)),
+            above: Box::new(number(1.5)),
+        };
+        assert_eq!(jump.value(7.9), 1.0);
+        assert_eq!(jump.value(8.0), 1.5);
+        assert_eq!(Expr::Speed.max(number(1.0)).value(0.5), 1.0);
+    }
+}
@@ -0,0 +1,44 @@
+use rawaccel_convert::types::{AccelArgs, Point};
+
+use crate::gui::check_curve;
+use crate::parameters::Parameter;
+
+pub struct SweepCurve {
+    pub value: f64,
+    pub points: Vec<Point>,
+}
+
+//`count` values from `from` to `to`, logarithmic parameters are spaced evenly on a log scale
+pub fn sweep_values(parameter: &Parameter, from: f64, to: f64, count: usize) -> Vec<f64> {
+    if count < 2 {
+        return vec![from];
+    }
+    return (0..count)
+        .map(|i| interpolate(parameter, from, to, i as f64 / (count - 1) as f64))
+        .collect();
+}
+
+//value `fraction` of the way from `from` to `to`, used by the scrubber between sweep values
+pub fn interpolate(parameter: &Parameter, from: f64, to: f64, fraction: f64) -> f64 {
+    if parameter.logarithmic && from > 0.0 && to > 0.0 {
+        return from * (to / from).powf(fraction);
+    }
+    return from + (to - from) * fraction;
+}
+
+//one generated curve per value, values rawaccel_convert can't generate a curve for are left out
+pub fn sweep(args: &AccelArgs, parameter: &Parameter, values: &[f64]) -> Vec<SweepCurve> {
+    let mut curves = vec![];
+    for value in values {
+        let mut sweep_args = args.clone();
+        (parameter.set)(&mut sweep_args, *value);
+        let curvegen = rawaccel_convert::generate_curve::generate_curve(&sweep_args);
+        if check_curve(&sweep_args, &curvegen).is_ok() {
+            curves.push(SweepCurve {
+                value: *value,
+                points: curvegen.points,
+            });
+        }
+    }
+    return curves;
+}
@@ -0,0 +1,292 @@
+const CM_PER_INCH: f64 = 2.54;
+//a log speed axis can't reach zero so it starts this many decades below the highest speed
+const LOG_SPEED_DECADES: i32 = 3;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum InputUnit {
+    CountsPerMs,
+    CmPerS,
+    InPerS,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum OutputUnit {
+    Sens,
+    Velocity,
+    Cm360,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct PlotUnits {
+    pub input: InputUnit,
+    pub output: OutputUnit,
+    pub log_x: bool,
+    pub log_y: bool,
+}
+
+impl Default for PlotUnits {
+    fn default() -> Self {
+        Self {
+            input: InputUnit::CountsPerMs,
+            output: OutputUnit::Sens,
+            log_x: false,
+            log_y: false,
+        }
+    }
+}
+
+impl InputUnit {
+    pub fn label(&self) -> &'static str {
+        match self {
+            InputUnit::CountsPerMs => "counts/ms",
+            InputUnit::CmPerS => "cm/s",
+            InputUnit::InPerS => "in/s",
+        }
+    }
+
+    pub fn speed_from_counts_per_ms(self, speed: f64, dpi: u32) -> f64 {
+        let inches_per_s = speed * 1000.0 / dpi as f64;
+        match self {
+            InputUnit::CountsPerMs => speed,
+            InputUnit::CmPerS => inches_per_s * CM_PER_INCH,
+            InputUnit::InPerS => inches_per_s,
+        }
+    }
+
+    pub fn speed_to_counts_per_ms(self, value: f64, dpi: u32) -> f64 {
+        let inches_per_s = match self {
+            InputUnit::CountsPerMs => return value,
+            InputUnit::CmPerS => value / CM_PER_INCH,
+            InputUnit::InPerS => value,
+        };
+        return inches_per_s * dpi as f64 / 1000.0;
+    }
+}
+
+impl OutputUnit {
+    pub fn label(&self) -> &'static str {
+        match self {
+            OutputUnit::Sens => "Sens",
+            OutputUnit::Velocity => "Velocity",
+            OutputUnit::Cm360 => "cm/360",
+        }
+    }
+}
+
+//how far the mouse moves for one full turn in game at a given sens multiplier
+pub fn cm_per_360(sens: f64, dpi: u32, yaw: f64, game_sens: f64) -> f64 {
+    let counts_per_360 = 360.0 / (yaw * game_sens * sens);
+    return counts_per_360 / dpi as f64 * CM_PER_INCH;
+}
+
+//inverse of `cm_per_360`, the sens multiplier giving a cm/360
+pub fn sens_for_cm_per_360(cm: f64, dpi: u32, yaw: f64, game_sens: f64) -> f64 {
+    let counts_per_360 = cm / CM_PER_INCH * dpi as f64;
+    return 360.0 / (yaw * game_sens * counts_per_360);
+}
+
+//maps points from the curve (counts/ms against sens or velocity) into the plotted units
+#[derive(Clone, Copy, PartialEq)]
+pub struct PlotTransform {
+    pub units: PlotUnits,
+    pub dpi: u32,
+    pub yaw: f64,
+    pub game_sens: f64,
+}
+
+impl PlotTransform {
+    pub fn point(&self, x: f64, y: f64) -> [f64; 2] {
+        return [self.x(x), self.y(y)];
+    }
+
+    pub fn x(&self, x: f64) -> f64 {
+        return log_axis(
+            self.units.input.speed_from_counts_per_ms(x, self.dpi),
+            self.units.log_x,
+        );
+    }
+
+    pub fn y(&self, y: f64) -> f64 {
+        let y = match self.units.output {
+            OutputUnit::Sens | OutputUnit::Velocity => y,
+            OutputUnit::Cm360 => cm_per_360(y, self.dpi, self.yaw, self.game_sens),
+        };
+        return log_axis(y, self.units.log_y);
+    }
+
+    pub fn inverse_x(&self, x: f64) -> f64 {
+        return self
+            .units
+            .input
+            .speed_to_counts_per_ms(self.axis_x(x), self.dpi);
+    }
+
+    pub fn inverse_y(&self, y: f64) -> f64 {
+        let y = self.axis_y(y);
+        match self.units.output {
+            OutputUnit::Sens | OutputUnit::Velocity => y,
+            OutputUnit::Cm360 => sens_for_cm_per_360(y, self.dpi, self.yaw, self.game_sens),
+        }
+    }
+
+    //value in the plotted unit at a plot coordinate, only differs on a log axis
+    pub fn axis_x(&self, x: f64) -> f64 {
+        return unlog_axis(x, self.units.log_x);
+    }
+
+    pub fn axis_y(&self, y: f64) -> f64 {
+        return unlog_axis(y, self.units.log_y);
+    }
+
+    //`min_speed` unless the speed axis is logarithmic and it would be zero or too far down
+    pub fn min_speed(&self, min_speed: f64, max_speed: f64) -> f64 {
+        if !self.units.log_x {
+            return min_speed;
+        }
+        return min_speed.max(max_speed * 10f64.powi(-LOG_SPEED_DECADES));
+    }
+
+    //the unit shown on the secondary axis
+    pub fn secondary_input(&self) -> InputUnit {
+        match self.units.input {
+            InputUnit::CountsPerMs => InputUnit::CmPerS,
+            InputUnit::CmPerS | InputUnit::InPerS => InputUnit::CountsPerMs,
+        }
+    }
+
+    //the secondary y axis shows cm/360 for sens and sens for cm/360, velocity has none
+    pub fn secondary_y(&self, y: f64) -> Option<f64> {
+        let y = self.axis_y(y);
+        match self.units.output {
+            OutputUnit::Sens => Some(cm_per_360(y, self.dpi, self.yaw, self.game_sens)),
+            OutputUnit::Velocity => None,
+            OutputUnit::Cm360 => Some(sens_for_cm_per_360(y, self.dpi, self.yaw, self.game_sens)),
+        }
+    }
+
+    pub fn secondary_y_label(&self) -> Option<&'static str> {
+        match self.units.output {
+            OutputUnit::Sens => Some(OutputUnit::Cm360.label()),
+            OutputUnit::Velocity => None,
+            OutputUnit::Cm360 => Some(OutputUnit::Sens.label()),
+        }
+    }
+}
+
+//log axes plot the base 10 log of the value
+fn log_axis(value: f64, log: bool) -> f64 {
+    match log {
+        true => value.log10(),
+        false => value,
+    }
+}
+
+fn unlog_axis(value: f64, log: bool) -> f64 {
+    match log {
+        true => 10f64.powf(value),
+        false => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT_UNITS: [InputUnit; 3] =
+        [InputUnit::CountsPerMs, InputUnit::CmPerS, InputUnit::InPerS];
+    const OUTPUT_UNITS: [OutputUnit; 3] =
+        [OutputUnit::Sens, OutputUnit::Velocity, OutputUnit::Cm360];
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() <= b.abs() * 1e-12, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn speed_conversions() {
+        //1 count/ms at 1000 dpi is 1 in/s
+        assert_close(InputUnit::InPerS.speed_from_counts_per_ms(1.0, 1000), 1.0);
+        assert_close(InputUnit::CmPerS.speed_from_counts_per_ms(1.0, 1000), 2.54);
+        assert_close(
+            InputUnit::CountsPerMs.speed_from_counts_per_ms(7.0, 1600),
+            7.0,
+        );
+        for unit in INPUT_UNITS {
+            for speed in [0.0, 0.5, 12.0, 300.0] {
+                let value = unit.speed_from_counts_per_ms(speed, 1600);
+                assert_close(unit.speed_to_counts_per_ms(value, 1600), speed);
+            }
+        }
+    }
+
+    #[test]
+    fn cm_per_360_round_trips() {
+        //0.022 degrees per count at 1600 dpi takes 16363.6 counts, 25.977 cm
+        assert_close(
+            cm_per_360(1.0, 1600, 0.022, 1.0),
+            360.0 / 0.022 / 1600.0 * 2.54,
+        );
+        //doubling the sens halves the distance
+        assert_close(
+            cm_per_360(2.0, 1600, 0.022, 1.0),
+            cm_per_360(1.0, 1600, 0.022, 1.0) / 2.0,
+        );
+        for sens in [0.25, 1.0, 3.5] {
+            let cm = cm_per_360(sens, 800, 0.022, 1.5);
+            assert_close(sens_for_cm_per_360(cm, 800, 0.022, 1.5), sens);
+        }
+    }
+
+    #[test]
+    fn plot_transform_round_trips() {
+        for input in INPUT_UNITS {
+            for output in OUTPUT_UNITS {
+                for (log_x, log_y) in [(false, false), (true, false), (false, true), (true, true)] {
+                    let transform = PlotTransform {
+                        units: PlotUnits {
+                            input,
+                            output,
+                            log_x,
+                            log_y,
+                        },
+                        dpi: 1600,
+                        yaw: 0.022,
+                        game_sens: 1.0,
+                    };
+                    for value in [0.01, 1.0, 42.0] {
+                        assert_close(transform.inverse_x(transform.x(value)), value);
+                        assert_close(transform.inverse_y(transform.y(value)), value);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn log_axes_plot_decades() {
+        let transform = PlotTransform {
+            units: PlotUnits {
+                input: InputUnit::CountsPerMs,
+                output: OutputUnit::Sens,
+                log_x: true,
+                log_y: true,
+            },
+            dpi: 1600,
+            yaw: 0.022,
+            game_sens: 1.0,
+        };
+        assert_close(transform.x(100.0), 2.0);
+        assert_close(transform.y(0.1), -1.0);
+        assert_close(transform.axis_x(2.0), 100.0);
+        assert_close(transform.axis_y(-1.0), 0.1);
+        assert_close(transform.min_speed(0.0, 100.0), 0.1);
+
+        let linear = PlotTransform {
+            units: PlotUnits::default(),
+            ..transform
+        };
+        assert_eq!(linear.x(100.0), 100.0);
+        assert_eq!(linear.axis_y(-1.0), -1.0);
+        assert_eq!(linear.min_speed(0.0, 100.0), 0.0);
+    }
+}
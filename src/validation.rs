@@ -0,0 +1,188 @@
+use rawaccel_convert::types::{AccelArgs, AccelMode, PointScaling};
+
+use crate::expression;
+use crate::gui::RawaccelConvertSettings;
+use crate::parameters::{self, Parameter};
+
+//the most points rawaccel accepts in a look up table
+const MAX_LOOKUP_POINTS: usize = 257;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+pub struct Problem {
+    pub severity: Severity,
+    //settings field the problem is about, see `field_id`
+    pub field: &'static str,
+    pub message: String,
+}
+
+//id of the sidebar text field for a settings field so problems can focus it
+pub fn field_id(field: &str) -> egui::Id {
+    return egui::Id::new(("settings_field", field));
+}
+
+pub fn has_errors(problems: &[Problem]) -> bool {
+    return problems
+        .iter()
+        .any(|problem| problem.severity == Severity::Error);
+}
+
+struct Validator {
+    problems: Vec<Problem>,
+}
+
+impl Validator {
+    fn error(&mut self, field: &'static str, message: String) {
+        self.problems.push(Problem {
+            severity: Severity::Error,
+            field,
+            message,
+        });
+    }
+
+    fn warning(&mut self, field: &'static str, message: String) {
+        self.problems.push(Problem {
+            severity: Severity::Warning,
+            field,
+            message,
+        });
+    }
+
+    //parse and range check from the parameter registry
+    fn parameter(
+        &mut self,
+        settings: &mut RawaccelConvertSettings,
+        parameter: &Parameter,
+    ) -> Option<f64> {
+        let value = match expression::evaluate((parameter.settings_string)(settings)) {
+            Ok(ok) => ok,
+            Err(err) => {
+                self.error(parameter.id, format!("{}: {}", parameter.label, err));
+                return None;
+            }
+        };
+        if !parameter.is_valid(value) {
+            self.error(
+                parameter.id,
+                format!("{} {}", parameter.label, parameter.valid_description()),
+            );
+            return None;
+        }
+        return Some(value);
+    }
+}
+
+//checks the sidebar fields shown for the current mode for values rawaccel would reject or misbehave with
+pub fn validate(
+    settings: &RawaccelConvertSettings,
+    args: &AccelArgs,
+    export_point_scaling: &PointScaling,
+) -> Vec<Problem> {
+    let mut validator = Validator { problems: vec![] };
+
+    match expression::evaluate_whole(&settings.dpi_string) {
+        Ok(0) => validator.error("dpi", "DPI must be above 0".to_string()),
+        Ok(_) => {}
+        Err(_) => validator.error("dpi", "DPI is not a whole number".to_string()),
+    }
+    //the registry hands out the settings strings mutably
+    let mut settings = settings.clone();
+    let mut values = vec![];
+    let shown_parameters = [
+        vec![&parameters::SENS_MULTIPLIER],
+        parameters::mode_parameters(args),
+    ]
+    .concat();
+    for parameter in shown_parameters {
+        if let Some(value) = validator.parameter(&mut settings, parameter) {
+            values.push((parameter.id, value));
+        }
+    }
+    let value = |id: &str| {
+        values
+            .iter()
+            .find(|(value_id, _)| *value_id == id)
+            .map(|(_, value)| *value)
+    };
+
+    //combinations the per field ranges can't express
+    if value("acceleration") == Some(0.0) {
+        validator.warning(
+            "acceleration",
+            "Acceleration of 0 leaves the curve flat".to_string(),
+        );
+    }
+    if let Some(cap_output) = value("cap_output") {
+        if args.gain && cap_output < 1.0 {
+            validator.error(
+                "cap_output",
+                "Cap: Output below 1 with gain makes the curve decelerate".to_string(),
+            );
+        }
+    }
+    if args.mode == AccelMode::Jump && value("smooth") == Some(0.0) {
+        validator.warning(
+            "smooth",
+            "Smooth of 0 makes the jump an instant step".to_string(),
+        );
+    }
+    if value("limit").is_some_and(|limit| limit < 1.0) {
+        validator.warning(
+            "limit",
+            "Limit below 1 makes the curve decelerate".to_string(),
+        );
+    }
+
+    if args.mode == AccelMode::Lookup {
+        match rawaccel_convert::args_parser::parse_lookup_table(&settings.lookup_table_string) {
+            Some(points) => {
+                if points.len() < 2 {
+                    validator.error(
+                        "lookup_table",
+                        "LUT Points needs at least 2 points".to_string(),
+                    );
+                }
+                if points.len() > MAX_LOOKUP_POINTS {
+                    validator.error(
+                        "lookup_table",
+                        format!(
+                            "LUT Points has {} points, rawaccel accepts at most {}",
+                            points.len(),
+                            MAX_LOOKUP_POINTS
+                        ),
+                    );
+                }
+                if points.windows(2).any(|pair| pair[1].x <= pair[0].x) {
+                    validator.error(
+                        "lookup_table",
+                        "LUT Points input speeds must be strictly increasing".to_string(),
+                    );
+                }
+                if points.iter().any(|point| point.x < 0.0 || point.y < 0.0) {
+                    validator.error(
+                        "lookup_table",
+                        "LUT Points can't contain negative values".to_string(),
+                    );
+                }
+            }
+            None => validator.error("lookup_table", "LUT Points could not be read".to_string()),
+        }
+    }
+
+    match export_point_scaling {
+        PointScaling::Libinput | PointScaling::LibinputDebug => {}
+        _ => match expression::evaluate_whole(&settings.point_count_string) {
+            Ok(ok) if ok > 1 => {}
+            _ => validator.error(
+                "point_count",
+                "Max Number of Points must be a whole number above 1".to_string(),
+            ),
+        },
+    }
+
+    return validator.problems;
+}